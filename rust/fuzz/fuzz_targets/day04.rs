@@ -0,0 +1,3 @@
+#![no_main]
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| advent_of_code_2023_fuzz::fuzz_day(4, data));
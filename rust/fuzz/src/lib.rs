@@ -0,0 +1,24 @@
+//! Fuzz targets of the days, one per day (`fuzz_targets/dayNN.rs`), all solving each part of the input they are given on its own.
+//! A parse error is a valid outcome, only a panic of the puzzle is a crash.
+//!
+//! Run a day from the crate of the puzzles with `cargo +nightly fuzz run day05 fuzz/corpus/day05 ../data -- -max_len=4096 -timeout=10`,
+//! `../data` seeding the corpus with the inputs. Reproduce a crash with `cargo +nightly fuzz run day05 fuzz/artifacts/day05/crash-<hash>`,
+//! then copy the file to `fuzz/regressions/day05/` for `cargo test` to replay it once fixed.
+
+use advent_of_code_2023::days;
+use advent_of_code_2023::error::PuzzleError;
+use advent_of_code_2023::utils::Dataset;
+
+pub fn fuzz_day(day: u8, data: &[u8]) {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let lines: Vec<String> = content.lines().map(String::from).collect();
+    let def = days::get(day).unwrap_or_else(|| panic!("Day {} isn't registered", day));
+    // the puzzle panics are caught by the runner, they are raised again for the fuzzer to record them
+    for result in def.solve_parts(&Dataset::Real, &lines) {
+        if let Err(PuzzleError::Panic { report, .. }) = result {
+            panic!("Day {} panicked {}", day, report);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::utils::{Dataset, Part};
+
+const HEADER: &str = "# Expected puzzle answers, keyed by day, part and dataset.
+# Missing entries can be filled by running with --record.
+";
+
+/// A puzzle answer value, kept as an integer when the solver produced one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Int(i128),
+    Text(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl Value {
+    fn parse(str: &str) -> Option<Value> {
+        if let Some(text) = str.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            return Some(Value::Text(text.replace("\\\"", "\"").replace("\\\\", "\\")));
+        }
+        str.replace('_', "").parse::<i128>().ok().map(Value::Int)
+    }
+
+    fn to_toml(&self) -> String {
+        match self {
+            Value::Int(v) => v.to_string(),
+            Value::Text(v) => format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\"")),
+        }
+    }
+}
+
+macro_rules! value_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Value {
+                fn from(v: $int) -> Value {
+                    Value::Int(v as i128)
+                }
+            }
+        )*
+    };
+}
+
+value_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Value {
+    fn from(v: String) -> Value {
+        Value::Text(v)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Value {
+        Value::Text(v.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartAnswer {
+    pub value: Value,
+    pub expected: Option<Value>,
+}
+
+/// Answers produced by a puzzle run, for one or both parts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answer {
+    pub part1: Option<PartAnswer>,
+    pub part2: Option<PartAnswer>,
+}
+
+impl Answer {
+    pub fn part<T: Into<Value>>(part: Part, value: T) -> Answer {
+        let answer = Some(PartAnswer {
+            value: value.into(),
+            expected: None,
+        });
+        match part {
+            Part::Part1 => Answer { part1: answer, part2: None },
+            Part::Part2 => Answer { part1: None, part2: answer },
+        }
+    }
+
+    pub fn both<T1: Into<Value>, T2: Into<Value>>(values: (T1, T2)) -> Answer {
+        Answer {
+            part1: Some(PartAnswer {
+                value: values.0.into(),
+                expected: None,
+            }),
+            part2: Some(PartAnswer {
+                value: values.1.into(),
+                expected: None,
+            }),
+        }
+    }
+
+    pub fn get(&self, part: Part) -> Option<&PartAnswer> {
+        match part {
+            Part::Part1 => self.part1.as_ref(),
+            Part::Part2 => self.part2.as_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, part: Part) -> Option<&mut PartAnswer> {
+        match part {
+            Part::Part1 => self.part1.as_mut(),
+            Part::Part2 => self.part2.as_mut(),
+        }
+    }
+
+    pub fn clear(&mut self, part: Part) {
+        match part {
+            Part::Part1 => self.part1 = None,
+            Part::Part2 => self.part2 = None,
+        }
+    }
+
+    /// Combines the answers of two separate part runs on the same dataset
+    pub fn merge(self, other: Answer) -> Answer {
+        Answer {
+            part1: self.part1.or(other.part1),
+            part2: self.part2.or(other.part2),
+        }
+    }
+}
+
+/// Expected answers loaded from a small TOML file:
+/// ```toml
+/// [day1]
+/// part1.test = 142
+/// part1.real = 55607
+/// ```
+pub struct AnswersStore {
+    path: PathBuf,
+    answers: Mutex<BTreeMap<u8, BTreeMap<String, Value>>>,
+    modified: AtomicBool,
+}
+
+static STORE: OnceLock<AnswersStore> = OnceLock::new();
+
+/// `part1.test`, `part1.real`, or `part1.test_<name>` for a named example
+fn answer_key(part: Part, data_set: &Dataset) -> String {
+    format!(
+        "{}.{}",
+        match part {
+            Part::Part1 => "part1",
+            Part::Part2 => "part2",
+        },
+        data_set.name()
+    )
+}
+
+impl AnswersStore {
+    /// Loads the store, a missing file being an empty store
+    pub fn load(path: &Path) -> Result<AnswersStore, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Cannot read {}: {}", path.display(), e)),
+        };
+        let mut answers: BTreeMap<u8, BTreeMap<String, Value>> = BTreeMap::new();
+        let mut curr_day: Option<u8> = None;
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || format!("{}:{}: invalid line '{}'", path.display(), index + 1, line);
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let day = section.trim().strip_prefix("day").and_then(|d| d.parse::<u8>().ok()).ok_or_else(error)?;
+                curr_day = Some(day);
+                continue;
+            }
+            let day = curr_day.ok_or_else(error)?;
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let value = Value::parse(value.trim()).ok_or_else(error)?;
+            answers.entry(day).or_default().insert(key.trim().to_string(), value);
+        }
+        Ok(AnswersStore {
+            path: path.to_path_buf(),
+            answers: Mutex::new(answers),
+            modified: AtomicBool::new(false),
+        })
+    }
+
+    /// Makes the store available to every run context
+    pub fn install(self) {
+        if STORE.set(self).is_err() {
+            panic!("Answers store already installed");
+        }
+    }
+
+    pub fn installed() -> Option<&'static AnswersStore> {
+        STORE.get()
+    }
+
+    pub fn expected(&self, day: u8, part: Part, data_set: &Dataset) -> Option<Value> {
+        let answers = self.answers.lock().unwrap();
+        answers.get(&day).and_then(|day_answers| day_answers.get(&answer_key(part, data_set))).cloned()
+    }
+
+    pub fn record(&self, day: u8, part: Part, data_set: &Dataset, value: &Value) {
+        let mut answers = self.answers.lock().unwrap();
+        answers.entry(day).or_default().insert(answer_key(part, data_set), value.clone());
+        self.modified.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified.load(Ordering::Relaxed)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let answers = self.answers.lock().unwrap();
+        let mut content = String::from(HEADER);
+        for (day, day_answers) in answers.iter() {
+            content.push_str(&format!("\n[day{}]\n", day));
+            for (key, value) in day_answers {
+                content.push_str(&format!("{} = {}\n", key, value.to_toml()));
+            }
+        }
+        fs::write(&self.path, content)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::days::{self, RunKind};
+use crate::report::RunReport;
+
+const HEADER: &str = "# Bench baseline: median durations in ms, keyed by day, part and dataset.
+# Written with --save-baseline, compared with --baseline.
+";
+
+pub const DEFAULT_THRESHOLD_PCT: f64 = 10.0;
+
+/// Median bench durations of a previous run
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: BTreeMap<u8, BTreeMap<String, f64>>,
+}
+
+/// A bench median slower than its baseline by more than the threshold
+#[derive(Debug)]
+pub struct Regression {
+    pub day: u8,
+    pub key: String,
+    pub baseline_ms: f64,
+    pub median_ms: f64,
+}
+
+impl Regression {
+    pub fn increase_pct(&self) -> f64 {
+        (self.median_ms / self.baseline_ms - 1.0) * 100.0
+    }
+}
+
+/// `part1.real`, or `all.real` for a run solving both parts. A simultaneous day always solving both parts, its runs
+/// are keyed `all` even when only one part is reported (`--part`).
+fn baseline_key(report: &RunReport) -> String {
+    let simultaneous = days::get(report.day).is_some_and(|def| def.kind == RunKind::Simultaneous);
+    match report.parts.as_slice() {
+        [part] if !simultaneous => format!("{}.{}", format!("{:?}", part.part).to_lowercase(), report.data_set.name()),
+        _ => format!("all.{}", report.data_set.name()),
+    }
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let mut baseline = Baseline::default();
+        let mut curr_day: Option<u8> = None;
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || format!("{}:{}: invalid line '{}'", path.display(), index + 1, line);
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                curr_day = Some(section.trim().strip_prefix("day").and_then(|d| d.parse::<u8>().ok()).ok_or_else(error)?);
+                continue;
+            }
+            let day = curr_day.ok_or_else(error)?;
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let median = value.trim().parse::<f64>().ok().filter(|median| *median >= 0.0).ok_or_else(error)?;
+            baseline.medians.entry(day).or_default().insert(key.trim().to_string(), median);
+        }
+        Ok(baseline)
+    }
+
+    pub fn from_reports(reports: &[RunReport]) -> Baseline {
+        let mut baseline = Baseline::default();
+        for report in reports {
+            if let Some(stats) = &report.bench {
+                baseline.medians.entry(report.day).or_default().insert(baseline_key(report), stats.median);
+            }
+        }
+        baseline
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut content = String::from(HEADER);
+        for (day, medians) in &self.medians {
+            content.push_str(&format!("\n[day{}]\n", day));
+            for (key, median) in medians {
+                content.push_str(&format!("{} = {:.6}\n", key, median));
+            }
+        }
+        fs::write(path, content).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    /// Benched runs whose median is more than `threshold_pct` percent above the baseline one
+    pub fn regressions(&self, reports: &[RunReport], threshold_pct: f64) -> Vec<Regression> {
+        reports
+            .iter()
+            .filter_map(|report| {
+                let stats = report.bench.as_ref()?;
+                let key = baseline_key(report);
+                // a zero median gives no ratio to compare with
+                let baseline_ms = *self.medians.get(&report.day)?.get(&key).filter(|median| **median > 0.0)?;
+                Some(Regression {
+                    day: report.day,
+                    key,
+                    baseline_ms,
+                    median_ms: stats.median,
+                })
+            })
+            .filter(|regression| regression.increase_pct() > threshold_pct)
+            .collect()
+    }
+}
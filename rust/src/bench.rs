@@ -0,0 +1,97 @@
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// How a bench run is done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed iterations run first
+    pub warmup: u16,
+    /// Fixed number of timed iterations, or `None` to iterate until the time budget is spent
+    pub iterations: Option<u16>,
+    pub budget: Duration,
+}
+
+const MIN_AUTO_ITERATIONS: usize = 10;
+const MAX_AUTO_ITERATIONS: usize = 100_000;
+
+impl BenchConfig {
+    pub const DEFAULT_WARMUP: u16 = 3;
+    pub const DEFAULT_BUDGET_MS: u64 = 1000;
+
+    /// Runs the warmup then the timed iterations, returning each iteration duration in ms, or the first error
+    pub fn measure<E>(&self, mut iteration: impl FnMut() -> Result<(), E>) -> Result<Vec<f64>, E> {
+        for _ in 0..self.warmup {
+            iteration()?;
+        }
+        let mut timings = vec![];
+        let start = Instant::now();
+        loop {
+            let done = match self.iterations {
+                Some(nb) => timings.len() >= nb as usize,
+                None => timings.len() >= MAX_AUTO_ITERATIONS || (timings.len() >= MIN_AUTO_ITERATIONS && start.elapsed() >= self.budget),
+            };
+            if done {
+                return Ok(timings);
+            }
+            let start_iteration = Instant::now();
+            iteration()?;
+            timings.push(start_iteration.elapsed().as_secs_f64() * 1000.0);
+        }
+    }
+}
+
+/// Statistics of the iteration timings, in ms, once the outliers are removed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub outliers: usize,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+/// Value at the given quantile of sorted values, linearly interpolated
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = (sorted.len() - 1) as f64 * q;
+    let (low, high) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (pos - low as f64)
+}
+
+impl Stats {
+    /// Outliers are the timings outside of the Tukey fences (1.5 interquartile range away from the quartiles)
+    pub fn compute(timings: &[f64]) -> Option<Stats> {
+        if timings.is_empty() {
+            return None;
+        }
+        let mut sorted = timings.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let (q1, q3) = (quantile(&sorted, 0.25), quantile(&sorted, 0.75));
+        let (low_fence, high_fence) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+        let kept: Vec<f64> = sorted.iter().copied().filter(|t| *t >= low_fence && *t <= high_fence).collect();
+        let mean = kept.iter().sum::<f64>() / kept.len() as f64;
+        let variance = kept.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / kept.len() as f64;
+        Some(Stats {
+            iterations: timings.len(),
+            outliers: timings.len() - kept.len(),
+            min: kept[0],
+            median: quantile(&kept, 0.5),
+            p95: quantile(&kept, 0.95),
+            max: kept[kept.len() - 1],
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.3} ms, median {:.3} ms, p95 {:.3} ms, max {:.3} ms, stddev {:.3} ms ({} iterations, {} outliers)",
+            self.min, self.median, self.p95, self.max, self.stddev, self.iterations, self.outliers
+        )
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use advent_of_code_2023::baseline;
+use advent_of_code_2023::bench::BenchConfig;
+use advent_of_code_2023::days::{self, RunKind};
+use advent_of_code_2023::inputs::DataDir;
+use advent_of_code_2023::logger::{LogFormat, Logger};
+use advent_of_code_2023::pool;
+use advent_of_code_2023::utils::{Dataset, LevelOverride, LogLevel, Part, RunOption};
+
+pub const USAGE: &str = "Usage: advent_of_code_2023 [OPTIONS]
+       advent_of_code_2023 new-day <DAY> [--title <TITLE>]
+
+Commands:
+  new-day <DAY>            Generate src/dayNN.rs from src/day.temp.rs, register it with its tests and fuzz target, and create its empty input files.
+                           Only the day after the last registered one can be created, the registry can't have missing days
+      --title <TITLE>      Title of the new day (defaults to Day <DAY>)
+
+Options:
+  -d, --day <DAYS>         Days to run, as a list of days or ranges (e.g. 5,12-17)
+  -p, --part <PART>        Only run the given part (1 or 2)
+  -s, --dataset <DATASET>  Only run the given dataset (test for all the examples, or real)
+  -e, --example <NAME>     Only run the named example (e.g. day_8_<NAME>_test.dat)
+      --data-dir <DIR>     Directory of the input files (defaults to $AOC_DATA_DIR, then ./data or ../data)
+  -b, --bench <NB|auto>    Benchmark each puzzle over <NB> iterations, or as many as fit in the time budget
+      --warmup <NB>        Untimed iterations run before benchmarking (default 3)
+      --budget <MS>        Time budget of an automatic benchmark, in ms (default 1000)
+      --save-baseline <FILE>
+                           Save the bench medians as a baseline in <FILE>
+      --baseline <FILE>    Compare the bench medians with the baseline saved in <FILE>
+      --threshold <PCT>    Median increase over the baseline reported as a regression (default 10)
+  -j, --jobs <NB|auto>     Run the days, parts and datasets in parallel on <NB> threads, or one per CPU
+  -D, --debug              Enable debug logs
+  -v, --verbosity <[DAY[/PART]=]LEVEL>
+                           Log level (error, warn, info, debug or trace) of every day, of a day or of a part
+                           of a day (e.g. 17/2=debug), the most specific one applying; may be repeated.
+                           Days solving both parts at once only take a level for the whole day
+  -q, --quiet              Only print the failures and the final summary
+      --log <TARGET>       Write the logs to stdout (default), stderr or the given file
+      --log-format <FORMAT>
+                           Format of the logs: text (default), or json for one JSON object per line
+  -r, --record             Record the computed answers missing from the answers file
+      --json <FILE>        Write a JSON summary of the results to <FILE> (- for the last line of stdout)
+      --junit <FILE>       Write a JUnit XML report of the results to <FILE> (- for stdout)
+  -w, --watch              Run the days again whenever one of their input files changes, a change of their
+                           source rebuilding the binary with cargo and restarting it
+  -l, --list               List the registered days and exit
+  -h, --help               Print this help";
+
+#[derive(Debug, Default)]
+pub struct Cli {
+    pub days: Option<Vec<u8>>,
+    pub part: Option<Part>,
+    pub dataset: Option<Dataset>,
+    pub data_dir: Option<String>,
+    pub bench: Option<BenchConfig>,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
+    pub threshold: f64,
+    pub jobs: Option<usize>,
+    pub json: Option<String>,
+    pub junit: Option<String>,
+    pub debug: bool,
+    pub quiet: bool,
+    pub verbosity: Vec<LevelOverride>,
+    pub log: Option<String>,
+    pub log_format: LogFormat,
+    pub record: bool,
+    pub watch: bool,
+    pub list: bool,
+    pub help: bool,
+    pub new_day: Option<u8>,
+    pub title: Option<String>,
+}
+
+impl Cli {
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Cli, String> {
+        let mut cli = Cli::default();
+        let mut args = args;
+        let (mut bench, mut warmup, mut budget_ms, mut threshold) = (None, None, None, None);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-d" | "--day" => cli.days = Some(parse_days(&next_value(&arg, &mut args)?)?),
+                "-p" | "--part" => cli.part = Some(parse_part(&next_value(&arg, &mut args)?)?),
+                "-s" | "--dataset" => cli.dataset = Some(parse_dataset(&next_value(&arg, &mut args)?)?),
+                "-e" | "--example" => cli.dataset = Some(Dataset::Example(next_value(&arg, &mut args)?)),
+                "--data-dir" => cli.data_dir = Some(next_value(&arg, &mut args)?),
+                "-b" | "--bench" => {
+                    let value = next_value(&arg, &mut args)?;
+                    bench = match value.as_str() {
+                        "auto" => Some(None),
+                        _ => Some(Some(parse_count(&value, "bench iterations count", 1)?)),
+                    };
+                }
+                "--warmup" => warmup = Some(parse_count(&next_value(&arg, &mut args)?, "warmup iterations count", 0)?),
+                "--budget" => budget_ms = Some(parse_count(&next_value(&arg, &mut args)?, "bench time budget", 1)?),
+                "--baseline" => cli.baseline = Some(next_value(&arg, &mut args)?),
+                "--save-baseline" => cli.save_baseline = Some(next_value(&arg, &mut args)?),
+                "--threshold" => threshold = Some(parse_count(&next_value(&arg, &mut args)?, "regression threshold", 0.0)?),
+                "-j" | "--jobs" => {
+                    let value = next_value(&arg, &mut args)?;
+                    cli.jobs = match value.as_str() {
+                        "auto" => Some(pool::default_threads()),
+                        _ => Some(parse_count(&value, "jobs count", 1)?),
+                    };
+                }
+                "--json" => cli.json = Some(next_value(&arg, &mut args)?),
+                "--junit" => cli.junit = Some(next_value(&arg, &mut args)?),
+                "-D" | "--debug" => cli.debug = true,
+                "-v" | "--verbosity" => cli.verbosity.push(parse_verbosity(&next_value(&arg, &mut args)?)?),
+                "-q" | "--quiet" => cli.quiet = true,
+                "--log" => cli.log = Some(next_value(&arg, &mut args)?),
+                "--log-format" => cli.log_format = parse_log_format(&next_value(&arg, &mut args)?)?,
+                "-r" | "--record" => cli.record = true,
+                "-w" | "--watch" => cli.watch = true,
+                "-l" | "--list" => cli.list = true,
+                "-h" | "--help" => cli.help = true,
+                "new-day" => cli.new_day = Some(parse_day(&next_value(&arg, &mut args)?)?),
+                "--title" => cli.title = Some(next_value(&arg, &mut args)?),
+                _ => return Err(format!("Unknown argument '{}'", arg)),
+            }
+        }
+        cli.bench = match bench {
+            Some(iterations) => Some(BenchConfig {
+                warmup: warmup.unwrap_or(BenchConfig::DEFAULT_WARMUP),
+                iterations,
+                budget: Duration::from_millis(budget_ms.unwrap_or(BenchConfig::DEFAULT_BUDGET_MS)),
+            }),
+            None if warmup.is_some() || budget_ms.is_some() => return Err("--warmup and --budget need --bench".to_string()),
+            None => None,
+        };
+        if cli.bench.is_none() && (cli.baseline.is_some() || cli.save_baseline.is_some() || threshold.is_some()) {
+            return Err("--baseline, --save-baseline and --threshold need --bench".to_string());
+        }
+        if cli.bench.is_some() && cli.jobs.is_some() {
+            return Err("--jobs can't be used with --bench, parallel runs would skew the timings".to_string());
+        }
+        if cli.watch && (cli.jobs.is_some() || cli.json.is_some() || cli.junit.is_some() || cli.baseline.is_some() || cli.save_baseline.is_some()) {
+            return Err("--watch can't be used with --jobs, --json, --junit, --baseline or --save-baseline".to_string());
+        }
+        if cli.title.is_some() && cli.new_day.is_none() {
+            return Err("--title needs new-day".to_string());
+        }
+        cli.threshold = threshold.unwrap_or(baseline::DEFAULT_THRESHOLD_PCT);
+        Ok(cli)
+    }
+
+    pub fn logger(&self) -> Result<Logger, String> {
+        match self.log.as_deref() {
+            None | Some("stdout") => Ok(Logger::stdout(self.log_format)),
+            Some("stderr") => Ok(Logger::stderr(self.log_format)),
+            Some(path) => Logger::file(self.log_format, Path::new(path)),
+        }
+    }
+
+    pub fn run_option<'a>(&'a self, data_dir: &'a DataDir) -> RunOption<'a> {
+        let mut options = RunOption::default(&self.days).data_dir(data_dir).level_overrides(&self.verbosity);
+        if self.debug {
+            options = options.debug();
+        }
+        if self.quiet {
+            options = options.quiet();
+        }
+        if self.record {
+            options = options.record();
+        }
+        if let Some(config) = self.bench {
+            options = options.bench(config);
+        }
+        if let Some(threads) = self.jobs {
+            options = options.parallel(threads);
+        }
+        if let Some(part) = self.part {
+            options = options.only(part);
+        }
+        if let Some(dataset) = &self.dataset {
+            options = options.only_dataset(dataset);
+        }
+        options
+    }
+}
+
+fn next_value(arg: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("Missing value for '{}'", arg))
+}
+
+fn parse_count<T: FromStr + PartialOrd + Display>(value: &str, what: &str, min: T) -> Result<T, String> {
+    value
+        .parse::<T>()
+        .ok()
+        .filter(|count| *count >= min)
+        .ok_or_else(|| format!("Invalid {} '{}' (expected a number of at least {})", what, value, min))
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    value
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("Invalid day '{}' (expected a number between 1 and 25)", value))
+}
+
+fn parse_days(value: &str) -> Result<Vec<u8>, String> {
+    let mut days: Vec<u8> = vec![];
+    for item in value.split(',') {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("Invalid days range '{}'", item));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::Part1),
+        "2" => Ok(Part::Part2),
+        _ => Err(format!("Invalid part '{}' (expected 1 or 2)", value)),
+    }
+}
+
+fn parse_log_level(value: &str) -> Result<LogLevel, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "error" => Ok(LogLevel::ERROR),
+        "warn" => Ok(LogLevel::WARN),
+        "info" => Ok(LogLevel::INFO),
+        "debug" => Ok(LogLevel::DEBUG),
+        "trace" => Ok(LogLevel::TRACE),
+        _ => Err(format!("Invalid log level '{}' (expected error, warn, info, debug or trace)", value)),
+    }
+}
+
+/// `debug`, `17=debug` or `17/2=debug`
+fn parse_verbosity(value: &str) -> Result<LevelOverride, String> {
+    let Some((target, level)) = value.split_once('=') else {
+        return Ok(LevelOverride {
+            day: None,
+            part: None,
+            level: parse_log_level(value)?,
+        });
+    };
+    let (day, part) = match target.split_once('/') {
+        Some((day, part)) => (parse_day(day)?, Some(parse_part(part)?)),
+        None => (parse_day(target)?, None),
+    };
+    if part.is_some() && days::get(day).is_some_and(|def| def.kind == RunKind::Simultaneous) {
+        return Err(format!("Day {} solves both parts at once, its level can't be set per part (use {}={})", day, day, level));
+    }
+    Ok(LevelOverride {
+        day: Some(day),
+        part,
+        level: parse_log_level(level)?,
+    })
+}
+
+fn parse_dataset(value: &str) -> Result<Dataset, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "test" => Ok(Dataset::Test),
+        "real" => Ok(Dataset::Real),
+        _ => Err(format!("Invalid dataset '{}' (expected test or real)", value)),
+    }
+}
+
+fn parse_log_format(value: &str) -> Result<LogFormat, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "text" => Ok(LogFormat::Text),
+        "json" => Ok(LogFormat::Json),
+        _ => Err(format!("Invalid log format '{}' (expected text or json)", value)),
+    }
+}
@@ -1,19 +1,20 @@
 use crate::{
-    check_result,
+    answers::Answer,
+    error::ParseError,
     utils::{Context, Part},
 };
 
-fn parse(lines: &Vec<String>) -> Vec<&String> {
-    return lines
+fn parse(lines: &Vec<String>) -> Result<Vec<&String>, ParseError> {
+    return Ok(lines
         .iter()
-        .collect();
+        .collect());
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let values = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let _values = context.phase("parse", || parse(lines))?;
     if context.is_part(Part::Part1) {
-        check_result!(context, 0, [0, 0]);
+        Err(ParseError::input("part 1 not implemented"))
     } else {
-        check_result!(context, 0, [0, 0]);
+        Err(ParseError::input("part 2 not implemented"))
     }
 }
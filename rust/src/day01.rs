@@ -1,5 +1,8 @@
 use crate::{
-    check_result, log,
+    answers::Answer,
+    check_result,
+    error::{InputLine, ParseError},
+    log,
     utils::{Context, Part},
 };
 
@@ -27,12 +30,13 @@ const FULL: &'static [&'static str] = &[
 ];
 
 #[allow(dead_code)]
-fn parse(lines: &Vec<String>, context: &Context) -> Vec<i32> {
+fn parse(lines: &Vec<String>, context: &Context) -> Result<Vec<i32>, ParseError> {
     let is_part_two = context.is_part(Part::Part2);
     let chars = if is_part_two { FULL } else { BASE };
     return lines
         .into_iter()
-        .map(|l| {
+        .enumerate()
+        .map(|(index, l)| {
             let first_digit = chars
                 .iter()
                 .map(|str| {
@@ -43,9 +47,10 @@ fn parse(lines: &Vec<String>, context: &Context) -> Vec<i32> {
                             .unwrap_or(std::i32::MAX),
                     )
                 })
+                .filter(|a| a.1 != std::i32::MAX)
                 .min_by(|a, b| a.1.cmp(&(b.1)))
                 .map(|a| to_number(a.0))
-                .unwrap_or(std::i32::MAX);
+                .ok_or_else(|| InputLine::new(index, l).error(l, "no digit in"))?;
             let second = chars
                 .iter()
                 .map(|str| (str, l.rfind(str).map(|found| found as i32).unwrap_or(-1)))
@@ -61,17 +66,13 @@ fn parse(lines: &Vec<String>, context: &Context) -> Vec<i32> {
                 &first_digit,
                 &second
             );
-            return first_digit * 10 + second;
+            return Ok(first_digit * 10 + second);
         })
         .collect();
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let values = parse(lines, context);
-    let result = values.into_iter().sum();
-    if context.is_part(Part::Part1) {
-        check_result!(context, result, [142, 55607]);
-    } else {
-        check_result!(context, result, [281, 55291]);
-    }
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let values = context.phase("parse", || parse(lines, context))?;
+    let result: i32 = values.into_iter().sum();
+    check_result!(context, result)
 }
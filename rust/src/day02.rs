@@ -1,5 +1,8 @@
 use crate::{
-    check_result, log,
+    answers::Answer,
+    check_result,
+    error::{checked_product, Checked, InputLine, ParseError},
+    log,
     utils::{Context, Part},
 };
 
@@ -14,12 +17,13 @@ struct Game {
     picks: PicksList,
 }
 
-fn parse(lines: &Vec<String>) -> Vec<Game> {
+fn parse(lines: &Vec<String>) -> Result<Vec<Game>, ParseError> {
     return lines
         .into_iter()
         .enumerate()
         .map(|(pos, l)| {
-            let parts: PicksList = l
+            let line = InputLine::new(pos, l);
+            let parts = l
                 .split(": ")
                 .last()
                 .unwrap_or("")
@@ -30,39 +34,40 @@ fn parse(lines: &Vec<String>) -> Vec<Game> {
                         green: 0,
                         blue: 0,
                     };
-                    picks_str.split(", ").for_each(|pick| {
+                    for pick in picks_str.split(", ") {
                         let mut pick_parts = pick.split_whitespace();
-                        let number = pick_parts
-                            .next()
-                            .map(|nb| nb.parse::<u32>().unwrap())
-                            .unwrap_or(0);
-                        pick_parts.last().map(|color_str| match color_str {
-                            "red" => picks.red += number,
-                            "green" => picks.green += number,
-                            "blue" => picks.blue += number,
-                            _ => panic!("Unkown color {}", color_str),
-                        });
-                    });
-                    return picks;
+                        let number = match pick_parts.next() {
+                            Some(nb) => line.number::<u32>(nb)?,
+                            None => 0,
+                        };
+                        match pick_parts.last() {
+                            Some("red") => picks.red = picks.red.add_or_err(number)?,
+                            Some("green") => picks.green = picks.green.add_or_err(number)?,
+                            Some("blue") => picks.blue = picks.blue.add_or_err(number)?,
+                            Some(color_str) => return Err(line.error(color_str, "unknown color")),
+                            None => (),
+                        }
+                    }
+                    return Ok(picks);
                 })
-                .collect();
-            return Game {
+                .collect::<Result<PicksList, ParseError>>()?;
+            return Ok(Game {
                 index: (pos + 1) as u32,
                 picks: parts,
-            };
+            });
         })
         .collect();
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let games = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let games = context.phase("parse", || parse(lines))?;
     if context.is_part(Part::Part1) {
         let expected_max = Pick {
             red: 12,
             green: 13,
             blue: 14,
         };
-        let result = games
+        let result: u32 = games
             .iter()
             .filter(|game| {
                 game.picks.iter().all(|pick| {
@@ -73,9 +78,9 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
             })
             .map(|game| game.index)
             .sum();
-        check_result!(context, result, [8, 2679]);
+        check_result!(context, result)
     } else {
-        let result = games
+        let result: u32 = games
             .iter()
             .map(|game| {
                 let mut max = Pick {
@@ -88,12 +93,12 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
                     max.green = max.green.max(pick.green);
                     max.blue = max.blue.max(pick.blue);
                 });
-                let final_contrib = max.red * max.blue * max.green;
+                let final_contrib = checked_product([max.red, max.blue, max.green])?;
                 log!(debug, context, "Debug {}", final_contrib);
-                return final_contrib;
+                return Ok(final_contrib);
             })
-            .sum();
+            .try_fold(0, |sum: u32, contrib| sum.add_or_err(contrib?))?;
 
-        check_result!(context, result, [2286, 77607]);
+        check_result!(context, result)
     }
 }
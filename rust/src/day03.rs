@@ -1,7 +1,9 @@
 use std::collections::BTreeSet;
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::{checked_product, grid_width, Checked, InputLine, ParseError},
     utils::{Context, Part},
 };
 
@@ -32,7 +34,7 @@ impl Relative {
         match self {
             Self::Same => true,
             Self::Before => curr_pos > 0,
-            Self::After => curr_pos < max_pos,
+            Self::After => curr_pos + 1 < max_pos,
         }
     }
 
@@ -73,7 +75,7 @@ impl Map {
 
     fn get(&self, pos: usize, x: &Relative, y: &Relative) -> Option<(&Cell,usize)> {
         if !x.is_valid(self.get_x_pos(pos), self.width)
-            || !y.is_valid(pos / self.height, self.height)
+            || !y.is_valid(pos / self.width, self.height)
         {
             return None;
         }
@@ -164,7 +166,14 @@ impl Map {
     }
 }
 
-fn parse(lines: &Vec<String>, is_part_two: bool) -> Map {
+fn parse(lines: &Vec<String>, is_part_two: bool) -> Result<Map, ParseError> {
+    let width = grid_width(lines)?;
+    for (index, line) in lines.iter().enumerate() {
+        // up to 9 digits, the numbers fitting in an u32
+        if let Some(number) = line.split(|c: char| !c.is_ascii_digit()).find(|number| number.len() > 9) {
+            return Err(InputLine::new(index, line).error(number, "number too large"));
+        }
+    }
     let map = lines
         .into_iter()
         .flat_map(|line| {
@@ -182,15 +191,15 @@ fn parse(lines: &Vec<String>, is_part_two: bool) -> Map {
             })
         })
         .collect();
-    return Map {
+    return Ok(Map {
         height: lines.len(),
-        width: lines.get(0).unwrap().len(),
+        width,
         map,
-    };
+    });
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let values = parse(lines, context.is_part(Part::Part2));
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let values = context.phase("parse", || parse(lines, context.is_part(Part::Part2)))?;
     if context.is_part(Part::Part1) {
         let result: u32 = values
             .map
@@ -201,9 +210,9 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
                 _ => false,
             })
             .filter_map(|(pos, _)| values.get_number_with_adjacent(pos))
-            .sum();
+            .try_fold(0, |sum: u32, number| sum.add_or_err(number))?;
 
-        check_result!(context, result, [4361, 550934]);
+        check_result!(context, result)
     } else {
         let all_tuples:Vec<(Relative,Relative)> = [Relative::Before,Relative::After,Relative::Same].iter()
         .flat_map(|x_rel| [Relative::Before,Relative::After,Relative::Same].iter().map(|y_iter| (x_rel.clone(),y_iter.clone())))
@@ -226,13 +235,38 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
                         matching
                     }).collect();
                 if start_numbers.len()>1 {
-                    let gear = start_numbers.iter().filter_map(|start_pos| values.get_number(*start_pos)).fold(1 as u32, |cumul,curr|cumul*curr);
+                    let gear = checked_product(start_numbers.iter().filter_map(|start_pos| values.get_number(*start_pos)));
                     Some(gear)
                 } else {None}
             })
-            .sum();
+            .try_fold(0, |sum: u32, gear| sum.add_or_err(gear?))?;
+
+
+        check_result!(context, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::Value;
+    use crate::days;
+    use crate::utils::{Dataset, Part};
 
+    fn part1(lines: &[&str]) -> Value {
+        let lines = lines.iter().map(|line| line.to_string()).collect();
+        days::get(3).unwrap().solve_part(Part::Part1, &Dataset::Real, &lines).unwrap().part1.unwrap().value
+    }
+
+    #[test]
+    fn last_column_isnt_next_to_the_first_column_of_the_next_row() {
+        assert_eq!(part1(&["..1", "#.."]), Value::from(0u32));
+        assert_eq!(part1(&["..1", ".#."]), Value::from(1u32));
+    }
 
-        check_result!(context, result, [467835, 81997870]);
+    /// Input found by the fuzzer (`fuzz/regressions/day03/`), the row of a cell being computed with the height instead of the width
+    #[test]
+    fn single_row_map() {
+        assert_eq!(part1(&["467...467.ZZZZZZZZZZZZ."]), Value::from(0u32));
+        assert_eq!(part1(&["467#"]), Value::from(467u32));
     }
 }
@@ -1,7 +1,9 @@
 use std::collections::BTreeSet;
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::{Checked, InputLine, ParseError},
     utils::{Context, Part},
 };
 
@@ -11,45 +13,50 @@ struct Card {
     owned: Vec<u8>,
 }
 
-fn parse(lines: &Vec<String>) -> Vec<Card> {
+fn parse(lines: &Vec<String>) -> Result<Vec<Card>, ParseError> {
+    if lines.is_empty() {
+        return Err(ParseError::empty_input());
+    }
     return lines
         .iter()
-        .map(|card_str| {
-            let (_, numbers) = card_str.split_once(":").unwrap();
-            let (winning_numbers_str, owned_numbers_str) = numbers.split_once("|").unwrap();
+        .enumerate()
+        .map(|(index, card_str)| {
+            let line = InputLine::new(index, card_str);
+            let (_, numbers) = line.split_once(card_str, ":")?;
+            let (winning_numbers_str, owned_numbers_str) = line.split_once(numbers, "|")?;
             let winning_numbers: BTreeSet<u8> = winning_numbers_str
                 .split_ascii_whitespace()
-                .map(|n| n.parse::<u8>().unwrap())
-                .collect();
+                .map(|n| line.number::<u8>(n))
+                .collect::<Result<_, _>>()?;
             let owned_numbers: Vec<u8> = owned_numbers_str
                 .split_ascii_whitespace()
-                .map(|n| n.parse::<u8>().unwrap())
-                .collect();
-            return Card {
+                .map(|n| line.number::<u8>(n))
+                .collect::<Result<_, _>>()?;
+            return Ok(Card {
                 nb_copies: 1,
                 winining: winning_numbers,
                 owned: owned_numbers,
-            };
+            });
         })
         .collect();
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let mut cards = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let mut cards = context.phase("parse", || parse(lines))?;
     if context.is_part(Part::Part1) {
         let result: u32 = cards
             .iter()
             .map(|c| {
                 let nb_wining = c.owned.iter().filter(|n| c.winining.contains(n)).count() as u32;
                 return if nb_wining == 0 {
-                    0
+                    Ok(0)
                 } else {
-                    (2 as u32).pow(nb_wining - 1)
+                    (2 as u32).checked_pow(nb_wining - 1).ok_or_else(ParseError::overflow)
                 };
             })
-            .sum();
+            .try_fold(0, |sum: u32, points| sum.add_or_err(points?))?;
 
-        check_result!(context, result, [13, 26346]);
+        check_result!(context, result)
     } else {
         let max_id = cards.len() - 1;
         let mut total = 0;
@@ -61,12 +68,12 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
                 .count();
             let last_pos = max_id.min(pos + nb_wining);
             let curr_card_count = cards[pos].nb_copies;
-            total += curr_card_count as u32;
+            total = curr_card_count.add_or_err(total)?;
             for to_update_pos in (pos + 1)..=last_pos {
-                cards[to_update_pos].nb_copies += curr_card_count;
+                cards[to_update_pos].nb_copies = cards[to_update_pos].nb_copies.add_or_err(curr_card_count)?;
             }
         }
 
-        check_result!(context, total, [30, 8467762]);
+        check_result!(context, total)
     }
 }
@@ -1,8 +1,11 @@
 use crate::{
+    answers::Answer,
     check_result,
+    error::{InputLine, ParseError},
     utils::{Context, Part},
 };
 
+/// Wide enough for the computations on the u32 numbers of the input not to overflow
 type Int = i64;
 
 type RangeDef = (Int, Int);
@@ -20,21 +23,21 @@ struct MapDef {
 
 type Input = (Vec<RangeDef>, Vec<MapDef>);
 
-fn parse(lines: &Vec<String>, is_part_two: bool) -> Input {
-    let seed_ranges = parse_seeds(lines, is_part_two);
-    let rules = parse_mapping_rules(lines);
-    return (seed_ranges, rules);
+fn parse(lines: &Vec<String>, is_part_two: bool) -> Result<Input, ParseError> {
+    let seed_ranges = parse_seeds(lines, is_part_two)?;
+    let rules = parse_mapping_rules(lines)?;
+    return Ok((seed_ranges, rules));
 }
 
-fn parse_seeds(lines: &Vec<String>, is_part_two: bool) -> Vec<(Int, Int)> {
-    let seeds_num = lines[0]
-        .split_once(":")
-        .unwrap()
+fn parse_seeds(lines: &Vec<String>, is_part_two: bool) -> Result<Vec<(Int, Int)>, ParseError> {
+    let line = InputLine::get(lines, 0)?;
+    let seeds_num = line
+        .split_once(line.text(), ":")?
         .1
         .split_ascii_whitespace()
-        .map(|str| str.parse::<Int>().unwrap())
-        .collect::<Vec<Int>>();
-    return if is_part_two {
+        .map(|str| line.number::<u32>(str).map(Int::from))
+        .collect::<Result<Vec<Int>, ParseError>>()?;
+    return Ok(if is_part_two {
         seeds_num
             .as_slice()
             .chunks_exact(2)
@@ -42,37 +45,44 @@ fn parse_seeds(lines: &Vec<String>, is_part_two: bool) -> Vec<(Int, Int)> {
             .collect()
     } else {
         seeds_num.iter().map(|n| (*n, *n)).collect()
-    };
+    });
 }
 
-fn parse_mapping_rules(lines: &Vec<String>) -> Vec<MapDef> {
-    let rules: Vec<MapDef> = lines
-        .as_slice()
-        .split(|line| line.trim().is_empty())
-        .skip(1)
-        .map(|pack| parse_map_def(pack))
-        .collect();
-    rules
+fn parse_mapping_rules(lines: &Vec<String>) -> Result<Vec<MapDef>, ParseError> {
+    let mut rules: Vec<MapDef> = vec![];
+    let mut first_index = 0;
+    for (pos, pack) in lines.as_slice().split(|line| line.trim().is_empty()).enumerate() {
+        if pos > 0 {
+            rules.push(parse_map_def(pack, first_index)?);
+        }
+        first_index += pack.len() + 1;
+    }
+    Ok(rules)
 }
 
-fn parse_map_def(pack: &[String]) -> MapDef {
+fn parse_map_def(pack: &[String], first_index: usize) -> Result<MapDef, ParseError> {
     let mut rules = pack
         .iter()
+        .enumerate()
         .skip(1)
-        .map(|rule_str| {
+        .map(|(index, rule_str)| {
+            let line = InputLine::new(first_index + index, rule_str);
             let ranges_def_str: Vec<&str> = rule_str.split_ascii_whitespace().collect();
+            if ranges_def_str.len() != 3 {
+                return Err(line.error(rule_str, "expected 3 numbers in"));
+            }
             let ranges_def = ranges_def_str
                 .iter()
-                .map(|n| n.parse::<Int>().unwrap())
-                .collect::<Vec<Int>>();
-            return MapRule {
+                .map(|n| line.number::<u32>(n).map(Int::from))
+                .collect::<Result<Vec<Int>, ParseError>>()?;
+            return Ok(MapRule {
                 range: (ranges_def[1], ranges_def[2] + ranges_def[1] - 1),
                 offset: ranges_def[0] - ranges_def[1],
-            };
+            });
         })
-        .collect::<Vec<MapRule>>();
+        .collect::<Result<Vec<MapRule>, ParseError>>()?;
     rules.sort_unstable_by(|a, b| a.range.0.cmp(&b.range.0));
-    return MapDef { rules };
+    return Ok(MapDef { rules });
 }
 
 struct SplitResult {
@@ -146,14 +156,11 @@ fn apply_maps(input: &Vec<MapDef>, orig: Vec<RangeDef>) -> Vec<RangeDef> {
         .fold(orig, |ranges, map_def| apply_map(ranges, map_def));
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let (seeds, map_defs) = parse(lines, context.is_part(Part::Part2));
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let (seeds, map_defs) = context.phase("parse", || parse(lines, context.is_part(Part::Part2)))?;
     let mut result = apply_maps(&map_defs, seeds);
     result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
 
-    if context.is_part(Part::Part1) {
-        check_result!(context, result[0].0, [35, 993500720]);
-    } else {
-        check_result!(context, result[0].0, [46, 4917124]);
-    }
+    let lowest = result.first().ok_or_else(|| ParseError::input("no seed"))?;
+    check_result!(context, lowest.0)
 }
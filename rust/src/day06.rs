@@ -1,7 +1,9 @@
 use std::ops::Div;
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::{checked_product, InputLine, ParseError},
     utils::{Context, Part},
 };
 
@@ -9,31 +11,28 @@ struct Race {
     time: f64,
     distance: f64,
 }
-fn parse(lines: &Vec<String>, is_part_two: bool) -> Vec<Race> {
-    let times_str = lines[0].split_once(":").unwrap().1.split_ascii_whitespace();
-    let distances_str = lines[1].split_once(":").unwrap().1.split_ascii_whitespace();
+fn parse(lines: &Vec<String>, is_part_two: bool) -> Result<Vec<Race>, ParseError> {
+    let (times_line, distances_line) = (InputLine::get(lines, 0)?, InputLine::get(lines, 1)?);
+    let times_str = times_line.split_once(times_line.text(), ":")?.1;
+    let distances_str = distances_line.split_once(distances_line.text(), ":")?.1;
 
     if is_part_two {
-        let time = times_str
-            .collect::<Vec<&str>>()
-            .join("")
-            .parse::<f64>()
-            .unwrap();
-        let distance = distances_str
-            .collect::<Vec<&str>>()
-            .join("")
-            .parse::<f64>()
-            .unwrap();
-        return vec![Race { time, distance }];
+        let time = times_line.number::<u64>(&times_str.split_ascii_whitespace().collect::<Vec<&str>>().join(""))? as f64;
+        let distance = distances_line.number::<u64>(&distances_str.split_ascii_whitespace().collect::<Vec<&str>>().join(""))? as f64;
+        return Ok(vec![Race { time, distance }]);
     }
 
-    let times = times_str.map(|n| n.parse::<f64>().unwrap());
-    let distances = distances_str.map(|n| n.parse::<f64>().unwrap());
+    let times = times_str.split_ascii_whitespace().map(|n| times_line.number::<u64>(n).map(|n| n as f64)).collect::<Result<Vec<f64>, ParseError>>()?;
+    let distances = distances_str
+        .split_ascii_whitespace()
+        .map(|n| distances_line.number::<u64>(n).map(|n| n as f64))
+        .collect::<Result<Vec<f64>, ParseError>>()?;
 
-    return times
+    return Ok(times
+        .into_iter()
         .zip(distances)
         .map(|(time, distance)| Race { time, distance })
-        .collect();
+        .collect());
 }
 
 fn calculate_roots(race: &Race)-> (f64,f64) {
@@ -44,24 +43,27 @@ fn calculate_roots(race: &Race)-> (f64,f64) {
 }
 
 const PRECISION:f64 = 0.000001;
-fn solve(race: &Race)-> u32 {
+fn solve(race: &Race)-> Result<u32, ParseError> {
     let (x1,x2) = calculate_roots(race);
+    // no root when the record can't be beaten
+    if x1.is_nan() || x2.is_nan() {
+        return Ok(0);
+    }
     let (x1_rounded,x2_rounded) = (x1.ceil(), x2.floor());
-    let min_time:u32 = (x1_rounded + if (x1_rounded-x1).abs()<PRECISION {1.0} else { 0.0 }) as u32;
-    let max_time:u32 = (race.time.min(x2_rounded - if (x2_rounded-x2).abs() <PRECISION { 1.0} else { 0.0 })) as u32;
-    let solution = max_time - min_time + 1;
-    return solution;
+    let min_time = x1_rounded + if (x1_rounded-x1).abs()<PRECISION {1.0} else { 0.0 };
+    let max_time = race.time.min(x2_rounded - if (x2_rounded-x2).abs() <PRECISION { 1.0} else { 0.0 });
+    let solution = (max_time - min_time + 1.0).max(0.0);
+    if solution > u32::MAX as f64 {
+        return Err(ParseError::overflow());
+    }
+    return Ok(solution as u32);
 }
 
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let values = parse(lines, context.is_part(Part::Part2));
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let values = context.phase("parse", || parse(lines, context.is_part(Part::Part2)))?;
 
-    let result = values.iter().map(solve).fold(1,|a, b| a * b);
+    let result = checked_product(values.iter().map(solve).collect::<Result<Vec<u32>, ParseError>>()?)?;
     
-    if context.is_part(Part::Part1) {
-        check_result!(context, result, [288, 4403592]);
-    } else {
-        check_result!(context, result, [71503, 38017587]);
-    }
+    check_result!(context, result)
 }
@@ -1,5 +1,7 @@
 use crate::{
+    answers::Answer,
     check_result,
+    error::{Checked, InputLine, ParseError},
     utils::{Context, Part},
 };
 
@@ -101,31 +103,34 @@ struct HandInfo {
     bid: u32,
 }
 
-fn parse(lines: &Vec<String>, is_part_one: bool) -> Vec<HandInfo> {
+const CARDS: &str = "23456789TJQKA";
+
+fn parse(lines: &Vec<String>, is_part_one: bool) -> Result<Vec<HandInfo>, ParseError> {
     return lines
         .iter()
-        .map(|l| {
-            let (hand, bid_str) = l.split_once(" ").unwrap();
-            let bid = bid_str.parse::<u32>().unwrap();
-            return HandInfo {
+        .enumerate()
+        .map(|(index, l)| {
+            let line = InputLine::new(index, l);
+            let (hand, bid_str) = line.split_once(l, " ")?;
+            if hand.len() != 5 || !hand.chars().all(|c| CARDS.contains(c)) {
+                return Err(line.error(hand, "invalid hand"));
+            }
+            let bid = line.number::<u32>(bid_str)?;
+            return Ok(HandInfo {
                 bid,
                 value: hand_value(hand, is_part_one),
-            };
+            });
         })
         .collect();
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let mut hands = parse(lines, context.is_part(Part::Part1));
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let mut hands = context.phase("parse", || parse(lines, context.is_part(Part::Part1)))?;
     hands.sort_unstable_by(|a, b| a.value.cmp(&b.value));
 
-    let result = hands.iter().enumerate().fold(0, |sum, (index, hand_info)| {
-        sum + hand_info.bid * (index as u32 + 1)
-    });
+    let result = hands.iter().enumerate().try_fold(0, |sum: u32, (index, hand_info)| {
+        sum.add_or_err(hand_info.bid.mul_or_err(index as u32 + 1)?)
+    })?;
 
-    if context.is_part(Part::Part1) {
-        check_result!(context, result, [6440, 253954294]);
-    } else {
-        check_result!(context, result, [5905, 254837398]);
-    }
+    check_result!(context, result)
 }
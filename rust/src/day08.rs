@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use crate::{check_result, utils::Context};
+use crate::{answers::Answer, check_result, error::{Checked, InputLine, ParseError}, utils::Context};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Direction {
@@ -24,8 +24,10 @@ struct Input {
     start_pos_part2: Vec<u16>,
     end_pos_part2: Vec<u16>,
 }
-fn parse(lines: &Vec<String>) -> Input {
-    let instructions = lines[0]
+fn parse(lines: &Vec<String>) -> Result<Input, ParseError> {
+    let instructions_line = InputLine::get(lines, 0)?;
+    let instructions = instructions_line
+        .text()
         .chars()
         .map(|c| {
             if c == 'L' {
@@ -35,9 +37,12 @@ fn parse(lines: &Vec<String>) -> Input {
             }
         })
         .collect::<Vec<Direction>>();
+    if instructions.is_empty() {
+        return Err(instructions_line.error(instructions_line.text(), "no instruction"));
+    }
     let mut input = Input {
         instructions,
-        rules: vec![(0, 0); lines.len() - 2],
+        rules: vec![(0, 0); lines.len().saturating_sub(2)],
         start_pos_part1: 0,
         end_pos_part1: 0,
         start_pos_part2: vec![],
@@ -45,14 +50,20 @@ fn parse(lines: &Vec<String>) -> Input {
     };
     let mut map: BTreeMap<&str, u16> = BTreeMap::new();
 
-    lines.iter().skip(2).for_each(|l| {
-        let (name, targets) = l.split_once(" = ").unwrap();
-        let (left, target) = targets[1..=8].split_once(", ").unwrap();
+    for (index, l) in lines.iter().enumerate().skip(2) {
+        let line = InputLine::new(index, l);
+        let (name, targets) = line.split_once(l, " = ")?;
+        let (left, target) = targets
+            .strip_prefix('(')
+            .and_then(|targets| targets.strip_suffix(')'))
+            .ok_or_else(|| line.error(targets, "invalid targets"))
+            .and_then(|targets| line.split_once(targets, ", "))?;
         let name_pos = get_pos(name, &mut map);
-        input.rules[name_pos as usize] = (
-            get_pos(left, &mut map) as u16,
-            get_pos(target, &mut map) as u16,
-        );
+        let targets_pos = (get_pos(left, &mut map), get_pos(target, &mut map));
+        if map.len() > input.rules.len() {
+            return Err(line.error(l, "unknown node in"));
+        }
+        input.rules[name_pos as usize] = targets_pos;
         if name.eq("AAA") {
             input.start_pos_part1 = name_pos
         }
@@ -66,16 +77,28 @@ fn parse(lines: &Vec<String>) -> Input {
         if name.ends_with('Z') {
             input.end_pos_part2.push(name_pos)
         }
-    });
+    }
+
+    Ok(input)
+}
 
-    input
+/// Steps after which a walk is looping without reaching its end, every (node, instruction) pair having been seen
+fn max_steps(input: &Input) -> u64 {
+    2 * input.rules.len() as u64 * input.instructions.len() as u64
 }
 
-fn solve_part1(input: &Input) -> u64 {
+fn no_end_reached() -> ParseError {
+    ParseError::input("the walk never reaches an end node")
+}
+
+fn solve_part1(input: &Input) -> Result<u64, ParseError> {
     let mut nb_steps: u32 = 0;
     let size = input.instructions.len() as usize;
     let mut curr_pos = input.start_pos_part1;
     while curr_pos != input.end_pos_part1 {
+        if nb_steps as u64 > max_steps(input) {
+            return Err(no_end_reached());
+        }
         nb_steps += 1;
         let direction = &input.instructions[(nb_steps as usize - 1) % size];
         let rules = input.rules[curr_pos as usize];
@@ -86,10 +109,10 @@ fn solve_part1(input: &Input) -> u64 {
         }
     }
 
-    nb_steps as u64
+    Ok(nb_steps as u64)
 }
 
-fn solve_part2(input: &Input) -> u64 {
+fn solve_part2(input: &Input) -> Result<u64, ParseError> {
     let mut loops_informations: Vec<(u32, u32)> = vec![(0, 0); input.start_pos_part2.len()];
     let mut curr_positions = input.start_pos_part2.clone();
     let mut nb_loops_finished = 0;
@@ -97,6 +120,9 @@ fn solve_part2(input: &Input) -> u64 {
     let size = input.instructions.len() as usize;
 
     while nb_loops_finished != loops_informations.len() {
+        if nb_steps as u64 > max_steps(input) {
+            return Err(no_end_reached());
+        }
         nb_steps += 1;
         let direction = &input.instructions[(nb_steps as usize - 1) % size];
         for pos in 0..curr_positions.len() {
@@ -139,10 +165,9 @@ fn solve_part2(input: &Input) -> u64 {
                 })
         });
 
-    return primes
-        .iter()
-        .zip(ppcm_parts)
-        .fold(1, |total, (prime, nb)| total * (prime.pow(nb)) as u64);
+    return primes.iter().zip(ppcm_parts).try_fold(1, |total: u64, (prime, nb)| {
+        total.mul_or_err((*prime as u64).checked_pow(nb).ok_or_else(ParseError::overflow)?)
+    });
 }
 
 fn decompose_primes(loop_info: u32, primes: &mut Vec<u32>) -> Vec<u8> {
@@ -180,14 +205,57 @@ fn get_next_prime(primes: &mut Vec<u32>, pos_prime: usize) -> u32 {
     primes[pos_prime]
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let input = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let input = context.phase("parse", || parse(lines))?;
 
-    let result_part1 = solve_part1(&input);
-    let result_part2 = solve_part2(&input);
-    check_result!(
-        context,
-        [result_part1, result_part2],
-        [6, 18827, 6, 20220305520997]
-    );
+    let result_part1 = context.phase("part1", || solve_part1(&input))?;
+    let result_part2 = context.phase("part2", || solve_part2(&input))?;
+    check_result!(context, [result_part1, result_part2])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days;
+    use crate::error::PuzzleError;
+    use crate::utils::Dataset;
+
+    fn solve(lines: &[&str]) -> Result<String, String> {
+        let lines = lines.iter().map(|line| line.to_string()).collect();
+        match days::get(8).unwrap().solve(&Dataset::Real, &lines) {
+            Ok(answer) => Ok(format!("{} {}", answer.part1.unwrap().value, answer.part2.unwrap().value)),
+            Err(PuzzleError::Parse { source, .. }) => Err(source.to_string()),
+            Err(error) => panic!("unexpected error {}", error),
+        }
+    }
+
+    #[test]
+    fn walks_reaching_their_end() {
+        assert_eq!(solve(&["L", "", "AAA = (ZZZ, ZZZ)", "ZZZ = (ZZZ, ZZZ)"]), Ok("1 1".to_string()));
+    }
+
+    /// A walk that can't reach its end is stopped once every node has been left with every instruction,
+    /// the input found by the fuzzer (`fuzz/regressions/day08/`) looping forever before
+    #[test]
+    fn walks_never_reaching_their_end() {
+        let error = solve(&["L", "", "AAA = (BBB, BBB)", "BBB = (BBB, BBB)", "ZZZ = (ZZZ, ZZZ)"]).unwrap_err();
+        assert!(error.contains("never reaches an end node"), "{}", error);
+        let lines = [
+            "LR",
+            "",
+            "11A = (11B, XXX)",
+            "11B = (XXX, 11Z)",
+            "11Z = (11B, XXX)",
+            "22A = (22B, XXX)",
+            "22B = (22C, 22C)",
+            "22C = (11Z, 22Z)",
+            "22Z = (22B, 22B)",
+            "XXX = (XXX, XXX)",
+        ];
+        assert!(solve(&lines).unwrap_err().contains("never reaches an end node"));
+    }
+
+    #[test]
+    fn instructions_cant_be_empty() {
+        assert!(solve(&["", "", "AAA = (ZZZ, ZZZ)", "ZZZ = (ZZZ, ZZZ)"]).unwrap_err().contains("no instruction"));
+    }
 }
@@ -1,56 +1,68 @@
 use crate::{
+    answers::Answer,
     check_result,
+    error::{Checked, InputLine, ParseError},
     utils::Context
 };
 
-fn parse(lines: &Vec<String>) -> Vec<Vec<i32>> {
+fn parse(lines: &Vec<String>) -> Result<Vec<Vec<i32>>, ParseError> {
     return lines
         .iter()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(|n| n.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
+        .enumerate()
+        .map(|(index, l)| {
+            let line = InputLine::new(index, l);
+            let history = l
+                .split_ascii_whitespace()
+                .map(|n| line.number::<i32>(n))
+                .collect::<Result<Vec<i32>, ParseError>>()?;
+            if history.is_empty() {
+                return Err(line.error(l, "empty history"));
+            }
+            Ok(history)
         })
         .collect();
 }
 
 struct NextHistory {
     array: Vec<i32>,
-    zeros: u16,
+    zeros: usize,
 }
 
-fn next_array(h: &Vec<i32>) -> NextHistory {
+fn next_array(h: &Vec<i32>) -> Result<NextHistory, ParseError> {
     let mut zeros = 0;
     let array = h
         .iter()
         .take(h.len() - 1)
         .enumerate()
         .map(|(index, v)| {
-            let new_value = h[index + 1] - *v;
+            let new_value = h[index + 1].sub_or_err(*v)?;
             if new_value == 0 {
                 zeros += 1;
             }
-            return new_value;
+            return Ok(new_value);
         })
-        .collect();
-    return NextHistory { zeros, array };
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+    return Ok(NextHistory { zeros, array });
 }
 
-fn predict(h: &Vec<i32>) -> (i32, i32) {
-    let next_array_info = next_array(h);
+fn predict(h: &Vec<i32>) -> Result<(i32, i32), ParseError> {
+    let next_array_info = next_array(h)?;
     let (curr_first, curr_last) = (h[0], h[h.len() - 1]);
-    if next_array_info.zeros == next_array_info.array.len() as u16 {
-        return (curr_first, curr_last);
+    if next_array_info.zeros == next_array_info.array.len() {
+        return Ok((curr_first, curr_last));
     }
-    let (before, after) = predict(&next_array_info.array);
-    return (curr_first - before, curr_last + after);
+    let (before, after) = predict(&next_array_info.array)?;
+    return Ok((curr_first.sub_or_err(before)?, curr_last.add_or_err(after)?));
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let values = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let values = context.phase("parse", || parse(lines))?;
     let (part2_res, part1_res) = values
         .iter()
         .map(|h| predict(h))
-        .fold((0, 0), |s, p| (s.0 + p.0, s.1 + p.1));
-    check_result!(context, [part1_res, part2_res], [114, 1921197370, 2, 1124]);
+        .try_fold((0, 0), |s: (i32, i32), p| {
+            let p = p?;
+            Ok::<_, ParseError>((s.0.add_or_err(p.0)?, s.1.add_or_err(p.1)?))
+        })?;
+    check_result!(context, [part1_res, part2_res])
 }
@@ -1,7 +1,9 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::ParseError,
     map2d::{Direction, DirectionAny, Map2D, Pos, TurnType, Vec2D},
     utils::Context,
 };
@@ -72,8 +74,13 @@ impl CellType {
         !(*self == CellType::Empty || *self == CellType::Start)
     }
 
-    fn map(c: char) -> CellType {
-        match c {
+    /// Whether the pipe continues the one left in the direction `dir`
+    fn is_connected_from(&self, dir: &Direction) -> bool {
+        self.is_to_explore() && self.dir_to_explore().contains(&dir.opposite())
+    }
+
+    fn map(c: char) -> Option<CellType> {
+        Some(match c {
             '|' => CellType::Vertical,
             '-' => CellType::Horizontal,
             'L' => CellType::CornerBottomLeft,
@@ -82,8 +89,8 @@ impl CellType {
             'F' => CellType::CornerTopLeft,
             '.' => CellType::Empty,
             'S' => CellType::Start,
-            _ => panic!("Unknown cell type {}", c),
-        }
+            _ => return None,
+        })
     }
 
     fn can_come_from_start(&self, world: &World, pos: &Pos) -> bool {
@@ -137,32 +144,24 @@ impl World {
     }
 }
 
-fn parse(lines: &Vec<String>) -> World {
-    let mut start = Pos { x: 0, y: 0 };
-    let content = lines
-        .iter()
-        .enumerate()
-        .map(|(y, l)| {
-            l.chars()
-                .enumerate()
-                .map(|(x, c)| {
-                    let cell_type = CellType::map(c);
-                    if cell_type == CellType::Start {
-                        start.x = x;
-                        start.y = y;
-                    }
-                    Cell {
-                        c_type: cell_type,
-                        fill_type: CellFillType::None,
-                    }
-                })
-                .collect::<Vec<Cell>>()
-        })
-        .collect();
-    return World {
-        map: Map2D::new(content),
-        start,
-    };
+fn parse(lines: &Vec<String>) -> Result<World, ParseError> {
+    let (map, starts) = Map2D::try_from_lines_marked(
+        lines,
+        |c, _| {
+            Ok(Cell {
+                c_type: CellType::map(c).ok_or("unknown cell type")?,
+                fill_type: CellFillType::None,
+            })
+        },
+        |cell| cell.c_type == CellType::Start,
+    )?;
+    if starts.len() > 1 {
+        return Err(ParseError::input("several start positions"));
+    }
+    return Ok(World {
+        map,
+        start: *starts.first().ok_or_else(|| ParseError::input("no start position"))?,
+    });
 }
 
 struct LoopInfo {
@@ -187,7 +186,7 @@ fn explore_one_more_for_loop<'a>(world: &World, curr_loop: &mut LoopInfo) -> Opt
             world
                 .map
                 .move_pos(&curr_pos, dir)
-                .filter(|next_pos| !next_pos.eq(&previous_pos) && world.map.get_opt(&next_pos).filter(|cell| cell.c_type.is_to_explore()).is_some())
+                .filter(|next_pos| !next_pos.eq(&previous_pos) && world.map.get_opt(&next_pos).filter(|cell| cell.c_type.is_connected_from(dir)).is_some())
         })
         .last();
     if let Some(next) = next_opt {
@@ -207,13 +206,13 @@ fn explore_one_more_for_loop<'a>(world: &World, curr_loop: &mut LoopInfo) -> Opt
     }
 }
 
-fn explore_one_more(map: &World, possible_loops: &mut Vec<LoopInfo>) -> FindLoopRes {
+fn explore_one_more(map: &World, possible_loops: &mut Vec<LoopInfo>) -> Result<FindLoopRes, ParseError> {
     let next_loop_infos = possible_loops
         .iter_mut()
         .filter_map(|loop_info| explore_one_more_for_loop(map, loop_info))
         .collect::<Vec<LoopInfo>>();
     if next_loop_infos.len() <= 1 {
-        panic!("Not enough loops")
+        return Err(ParseError::input("no loop going through the start position"));
     }
 
     let mut found_results = None;
@@ -237,16 +236,16 @@ fn explore_one_more(map: &World, possible_loops: &mut Vec<LoopInfo>) -> FindLoop
         all_cells.extend(second.all_cells.borrow().iter().skip(1).rev().skip(1));
         let nb_positive_turn = first.total_positive_turns - second.total_positive_turns;
 
-        return FindLoopRes::Found(LoopInfo {
+        return Ok(FindLoopRes::Found(LoopInfo {
             all_cells: Rc::new(RefCell::new(all_cells)),
             total_positive_turns: nb_positive_turn,
-        });
+        }));
     } else {
-        return FindLoopRes::ToExplore(next_loop_infos);
+        return Ok(FindLoopRes::ToExplore(next_loop_infos));
     }
 }
 
-fn find_loop(world: &World) -> LoopInfo {
+fn find_loop(world: &World) -> Result<LoopInfo, ParseError> {
     let mut curr_loops = Direction::ALL_DIRECTIONS_CLOCKWISE
         .iter()
         .filter_map(|dir| {
@@ -274,11 +273,11 @@ fn find_loop(world: &World) -> LoopInfo {
         .collect::<Vec<LoopInfo>>();
 
     loop {
-        match explore_one_more(world, &mut curr_loops) {
+        match explore_one_more(world, &mut curr_loops)? {
             FindLoopRes::ToExplore(next_loops) => {
                 curr_loops = next_loops;
             }
-            FindLoopRes::Found(result) => return result,
+            FindLoopRes::Found(result) => return Ok(result),
         }
     }
 }
@@ -331,17 +330,48 @@ fn mark_borders(map: &mut World, loop_info: &LoopInfo) {
     loop_info.all_cells.borrow().iter().for_each(|p| map.mark_as_border(p));
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let mut map = parse(lines);
-    let loop_info = find_loop(&map);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let mut map = context.phase("parse", || parse(lines))?;
+    let loop_info = context.phase("part1", || find_loop(&map))?;
     let distance_end_loop = (loop_info.all_cells.borrow().len() as u32).div_euclid(2);
 
-    mark_borders(&mut map, &loop_info);
-    let filled = loop_info
-        .all_cells
-        .borrow()
-        .windows(3)
-        .map(|window| fill(&mut map, window, loop_info.total_positive_turns > 0))
-        .sum();
-    check_result!(context, [distance_end_loop, filled], [80, 6909, 10, 461]);
+    let filled: u32 = context.phase("part2", || {
+        mark_borders(&mut map, &loop_info);
+        loop_info
+            .all_cells
+            .borrow()
+            .windows(3)
+            .map(|window| fill(&mut map, window, loop_info.total_positive_turns > 0))
+            .sum()
+    });
+    check_result!(context, [distance_end_loop, filled])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days;
+    use crate::utils::Dataset;
+
+    fn solve(lines: &[&str]) -> Result<String, String> {
+        let lines = lines.iter().map(|line| line.to_string()).collect();
+        match days::get(10).unwrap().solve(&Dataset::Real, &lines) {
+            Ok(answer) => Ok(format!("{} {}", answer.part1.unwrap().value, answer.part2.unwrap().value)),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    /// Inputs derived from the one found by the fuzzer (`fuzz/regressions/day10/`), where pipes next to a path but not connected
+    /// to it were followed, ending in a loop going back on itself
+    #[test]
+    fn only_connected_pipes_are_followed() {
+        assert_eq!(solve(&["FF7F-7FFF77F7F7F---7", "L|||FS7FFF77F7FF---7", "L7JLJL-JLJLJL--JLJ.L"]), Ok("4 0".to_string()));
+        let error = solve(&["FF7FS7FFF77F7F7F---7", "L|||F-7FFF77F7FF---7", "L7JLJL-JLJLJL--JLJ.L"]).unwrap_err();
+        assert!(error.contains("no loop going through the start position"), "{}", error);
+    }
+
+    #[test]
+    fn a_single_start_position() {
+        let error = solve(&["FF7FS7FFF77F7F7F---7", "L|||FS7FFF77F7FF---7", "L7JLJL-JLJLJL--JLJ.L"]).unwrap_err();
+        assert!(error.contains("several start positions"), "{}", error);
+    }
 }
@@ -1,7 +1,10 @@
 use std::collections::{BTreeSet, BTreeMap};
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::ParseError,
+    map2d::Map2D,
     utils::{Context, Part},
 };
 
@@ -26,26 +29,29 @@ fn build_offset_map(max: usize, non_empty: &BTreeSet<usize>, context:&Context)->
     return result;
 }
 
-fn solve(lines: &Vec<String>,context: &Context) -> usize {
+/// Positions of the galaxies ('#') of the image, every other cell being empty ('.')
+fn parse(lines: &[String]) -> Result<Vec<Star>, ParseError> {
+    let (_, galaxies) = Map2D::try_from_lines_marked(
+        lines,
+        |c, _| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => Err("unknown cell type"),
+        },
+        |is_galaxy| *is_galaxy,
+    )?;
+    Ok(galaxies.into_iter().map(|pos| Star { x: pos.x, y: pos.y }).collect())
+}
+
+fn solve(stars: &[Star], context: &Context) -> usize {
     let mut used:(BTreeSet<usize>,BTreeSet<usize>) = (BTreeSet::new(),BTreeSet::new());
     let mut max:(usize,usize) = (0,0);
-    let stars= lines
-        .iter()
-        .enumerate()
-        .flat_map(|(y,l)|{
-            l.chars().enumerate().filter_map(|(x,c)|
-                if c=='.' {
-                    None
-                } else {
-                    used.0.insert(x);
-                    used.1.insert(y);
-                    max.0=max.0.max(x);
-                    max.1=max.1.max(y);
-                    Some(Star{x,y})
-                }
-            ).collect::<Vec<Star>>()
-        })
-        .collect::<Vec<Star>>();
+    for s in stars {
+        used.0.insert(s.x);
+        used.1.insert(s.y);
+        max.0=max.0.max(s.x);
+        max.1=max.1.max(s.y);
+    }
     let offset_x_map = build_offset_map(max.0, &used.0,  context);
     let offset_y_map = build_offset_map(max.1, &used.1,  context);
 
@@ -59,11 +65,40 @@ fn solve(lines: &Vec<String>,context: &Context) -> usize {
         })
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let result = solve(lines,context);
-    if context.is_part(Part::Part1) {
-        check_result!(context, result, [374, 9693756]);
-    } else {
-        check_result!(context, result, [8410, 717878258016]);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let stars = context.phase("parse", || parse(lines))?;
+    let result = solve(&stars,context);
+    check_result!(context, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days;
+    use crate::utils::{Dataset, Part};
+
+    fn solve(lines: &[&str]) -> Result<String, String> {
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        match days::get(11).unwrap().solve_part(Part::Part1, &Dataset::Real, &lines) {
+            Ok(answer) => Ok(answer.part1.unwrap().value.to_string()),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    #[test]
+    fn distances_in_the_expanded_image() {
+        assert_eq!(solve(&["#..", "...", "..#"]), Ok("6".to_string()));
+        assert_eq!(solve(&["...", "..."]), Ok("0".to_string()));
+    }
+
+    fn assert_rejected(lines: &[&str], message: &str) {
+        let error = solve(lines).unwrap_err();
+        assert!(error.contains(message), "{}", error);
+    }
+
+    #[test]
+    fn images_are_grids_of_galaxies_and_empty_space() {
+        assert_rejected(&[], "empty input");
+        assert_rejected(&["#..", "#."], "line 2, column 1: width isn't 3 in '#.'");
+        assert_rejected(&["#..", ".x."], "line 2, column 2: unknown cell type 'x'");
     }
 }
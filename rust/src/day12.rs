@@ -1,7 +1,10 @@
 use rustc_hash::FxHashMap;
 
 use crate::{
-    check_result, log,
+    answers::Answer,
+    check_result,
+    error::{Checked, InputLine, ParseError},
+    log,
     utils::{Context, Part},
 };
 
@@ -13,12 +16,27 @@ struct ToSolve<'a> {
 }
 
 const TO_REPEAT: [u8; 5] = [1, 2, 3, 4, 5];
-fn parse(lines: &Vec<String>, is_part_two: bool) -> Vec<Input> {
+/// Longest pattern and group list accepted, a deliberate limit for the positions and groups of the unfolded pattern
+/// (5 × 50 + 4 springs) to fit in an u8
+const MAX_LEN: usize = 50;
+fn parse(lines: &Vec<String>, is_part_two: bool) -> Result<Vec<Input>, ParseError> {
     return lines
         .iter()
-        .map(|l| {
-            let (base_pattern, groups_str) = l.split_once(" ").unwrap();
-            let base_group = groups_str.split(",").map(|n| n.parse::<u8>().unwrap()).collect::<Vec<u8>>();
+        .enumerate()
+        .map(|(index, l)| {
+            let line = InputLine::new(index, l);
+            let (base_pattern, groups_str) = line.split_once(l, " ")?;
+            let base_group = groups_str.split(",").map(|n| line.number::<u8>(n)).collect::<Result<Vec<u8>, ParseError>>()?;
+            // rejected on purpose, rather than solved: no valid puzzle line has them
+            if base_pattern.is_empty() || base_pattern.len() > MAX_LEN || base_group.len() > MAX_LEN {
+                return Err(line.error(l, format!("expected 1 to {} springs and groups in", MAX_LEN)));
+            }
+            if let Some((index, c)) = base_pattern.char_indices().find(|(_, c)| !".#?".contains(*c)) {
+                return Err(line.error(&base_pattern[index..index + c.len_utf8()], "invalid spring"));
+            }
+            if base_group.contains(&0) {
+                return Err(line.error(groups_str, "empty group in"));
+            }
             let pattern = if is_part_two {
                 TO_REPEAT.iter().map(|_| base_pattern).collect::<Vec<&str>>().join("?")
             } else {
@@ -29,7 +47,7 @@ fn parse(lines: &Vec<String>, is_part_two: bool) -> Vec<Input> {
             } else {
                 base_group
             };
-            return (pattern, groups);
+            return Ok((pattern, groups));
         })
         .collect();
 }
@@ -86,17 +104,18 @@ fn find_matching_end(curr_str_start_idx: &usize, grp_size: &usize, str: &[u8], i
     return Some(curr_pos);
 }
 
-fn explore_next_solution(to_solve: &ToSolve, next_group_idx: u8, next_pos: u8, caches: &mut Caches) -> u64 {
+/// Number of arrangements, `None` when it overflows
+fn explore_next_solution(to_solve: &ToSolve, next_group_idx: u8, next_pos: u8, caches: &mut Caches) -> Option<u64> {
     let new_state = State {
         curr_group_idx: next_group_idx,
         curr_str_idx: next_pos,
     };
     if let Some(res) = caches.memoized.get(&new_state) {
-        return *res;
+        return Some(*res);
     } else {
-        let result = find_solutions(to_solve, &new_state, caches);
+        let result = find_solutions(to_solve, &new_state, caches)?;
         caches.memoized.insert(new_state, result);
-        return result;
+        return Some(result);
     }
 }
 
@@ -110,9 +129,9 @@ fn check_end_of_string(curr_str_start_idx: &u8, str: &[u8])->u64 {
     }
     return 1;
 }
-fn find_solutions(to_solve: &ToSolve, state: &State, caches: &mut Caches) -> u64 {
+fn find_solutions(to_solve: &ToSolve, state: &State, caches: &mut Caches) -> Option<u64> {
     if to_solve.groups.len() as u8 == state.curr_group_idx {
-        return check_end_of_string(&state.curr_str_idx, to_solve.str);
+        return Some(check_end_of_string(&state.curr_str_idx, to_solve.str));
     }
 
     let mut curr_str_start_idx = state.curr_str_idx as usize;
@@ -122,9 +141,9 @@ fn find_solutions(to_solve: &ToSolve, state: &State, caches: &mut Caches) -> u64
     loop {
         if let Some(end_pos) = find_matching_end(&curr_str_start_idx, &grp_size, to_solve.str, is_last_group) {
             if let Some(next_pos) = skip_dots(end_pos as u8, to_solve.str) {
-                total += explore_next_solution(to_solve, state.curr_group_idx + 1, next_pos, caches)
+                total = total.checked_add(explore_next_solution(to_solve, state.curr_group_idx + 1, next_pos, caches)?)?
             } else {
-                total += if is_last_group { 1 } else { 0 };
+                total = total.checked_add(if is_last_group { 1 } else { 0 })?;
                 break;
             }
         }
@@ -138,11 +157,11 @@ fn find_solutions(to_solve: &ToSolve, state: &State, caches: &mut Caches) -> u64
         curr_str_start_idx += 1
     }
 
-    return total;
+    return Some(total);
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let values = parse(lines, context.is_part(Part::Part2));
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let values = context.phase("parse", || parse(lines, context.is_part(Part::Part2)))?;
     let result: u64 = values
         .iter()
         .map(|input| {
@@ -160,13 +179,48 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
                 },
                 &mut caches,
             );
-            log!(debug, context, "Result of {} : {}", input.0, result);
-            return result;
+            log!(debug, context, "Result of {} : {:?}", input.0, result);
+            return result.ok_or_else(ParseError::overflow);
         })
-        .sum();
-    if context.is_part(Part::Part1) {
-        check_result!(context, result, [21, 7118]);
-    } else {
-        check_result!(context, result, [525152, 7030194981795]);
+        .try_fold(0, |sum: u64, result| sum.add_or_err(result?))?;
+    check_result!(context, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days;
+    use crate::utils::{Dataset, Part};
+
+    fn part1(lines: &[&str]) -> Result<String, String> {
+        let lines = lines.iter().map(|line| line.to_string()).collect();
+        match days::get(12).unwrap().solve_part(Part::Part1, &Dataset::Real, &lines) {
+            Ok(answer) => Ok(answer.part1.unwrap().value.to_string()),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    fn assert_rejected(line: &str, message: &str) {
+        let error = part1(&[line]).unwrap_err();
+        assert!(error.contains(message), "{}", error);
+    }
+
+    #[test]
+    fn arrangements_of_a_line() {
+        assert_eq!(part1(&["?###???????? 3,2,1"]), Ok("10".to_string()));
+    }
+
+    /// The empty pattern is the line of the input found by the fuzzer (`fuzz/regressions/day12/`)
+    #[test]
+    fn patterns_are_one_to_fifty_springs() {
+        assert_rejected(" 1,1,3", "expected 1 to 50 springs and groups");
+        assert_rejected(&format!("{} 1", "?".repeat(51)), "expected 1 to 50 springs and groups");
+        assert_rejected(&format!("# {}", vec!["1"; 51].join(",")), "expected 1 to 50 springs and groups");
+        assert_eq!(part1(&[&format!("{} 50", "#".repeat(50))]), Ok("1".to_string()));
+    }
+
+    #[test]
+    fn springs_and_groups_are_checked() {
+        assert_rejected("?=# 1", "invalid spring");
+        assert_rejected("?.# 1,0", "empty group");
     }
 }
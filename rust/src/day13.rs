@@ -1,36 +1,51 @@
 use crate::{
+    answers::Answer,
     check_result,
+    error::{InputLine, ParseError},
     utils::Context,
 };
 
 struct Pattern {
+    /// Index of the first line of the pattern in the input
+    line_index: usize,
+    first_line: String,
     horizontal: Vec<i32>,
     vertical: Vec<i32>,
     all: Vec<Vec<i32>>,
 }
 
-fn parse(lines: &Vec<String>) -> Vec<Pattern> {
-    return lines
-        .iter()
-        .as_slice()
-        .split(|l| l.trim().len() == 0)
-        .map(|pack| {
+fn parse(lines: &Vec<String>) -> Result<Vec<Pattern>, ParseError> {
+    let mut patterns = vec![];
+    let mut first_index = 0;
+    for pack in lines.iter().as_slice().split(|l| l.trim().len() == 0) {
+        if let Some(first) = pack.first() {
             let mut pattern = Pattern {
-                horizontal: vec![0; pack[0].len()],
+                line_index: first_index,
+                first_line: first.clone(),
+                horizontal: vec![0; first.len()],
                 vertical: vec![0; pack.len()],
-                all: vec![vec![0; pack[0].len()]; pack.len()],
+                all: vec![vec![0; first.len()]; pack.len()],
             };
-            pack.iter().enumerate().for_each(|(y, c)| {
-                c.chars().enumerate().for_each(|(x, c)| {
+            // a deliberate input limit, the rows and columns being stored as the bits of an i32
+            if first.len() > 31 || pack.len() > 31 {
+                return Err(InputLine::new(first_index, first).error(first, "pattern larger than 31 cells in"));
+            }
+            for (y, l) in pack.iter().enumerate() {
+                if l.len() != first.len() {
+                    return Err(InputLine::new(first_index + y, l).error(l, format!("width isn't {} in", first.len())));
+                }
+                l.chars().enumerate().for_each(|(x, c)| {
                     let value = if c == '#' { 1 } else { 0 };
                     pattern.horizontal[x] += value * 2_i32.pow(y as u32);
                     pattern.vertical[y] += value * 2_i32.pow(x as u32);
                     pattern.all[y][x] = value;
                 })
-            });
-            return pattern;
-        })
-        .collect();
+            }
+            patterns.push(pattern);
+        }
+        first_index += pack.len() + 1;
+    }
+    return Ok(patterns);
 }
 
 fn find_mirror(values: &Vec<i32>, map: impl Fn(usize) -> usize, to_ignore: &Option<usize>) -> Option<usize> {
@@ -65,8 +80,9 @@ fn find_mirror_value(pattern: &Pattern, to_ignore: &Option<usize>) -> Option<usi
     None
 }
 
-fn find_all_parts(pattern: &mut Pattern) -> (usize, usize) {
-    let standard_result = find_mirror_value(pattern, &None).unwrap();
+fn find_all_parts(pattern: &mut Pattern) -> Result<(usize, usize), ParseError> {
+    let no_mirror = |pattern: &Pattern| InputLine::new(pattern.line_index, &pattern.first_line).error(&pattern.first_line, "no mirror for the pattern");
+    let standard_result = find_mirror_value(pattern, &None).ok_or_else(|| no_mirror(pattern))?;
     let to_ignore = Some(standard_result);
     for x in 0..pattern.horizontal.len() {
         for y in 0..pattern.vertical.len() {
@@ -74,20 +90,51 @@ fn find_all_parts(pattern: &mut Pattern) -> (usize, usize) {
             pattern.horizontal[x] -= curr_value * 2_i32.pow(y as u32);
             pattern.vertical[y] -= curr_value * 2_i32.pow(x as u32);
             if let Some(v) = find_mirror_value(pattern, &to_ignore) {
-                return (standard_result, v);
+                return Ok((standard_result, v));
             }
             pattern.horizontal[x] += curr_value * 2_i32.pow(y as u32);
             pattern.vertical[y] += curr_value * 2_i32.pow(x as u32);
         }
     }
-    panic!("Not found");
+    Err(no_mirror(pattern))
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let mut values = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let mut values = context.phase("parse", || parse(lines))?;
     let result: (usize, usize) = values
         .iter_mut()
         .map(|p| find_all_parts(p))
-        .fold((0, 0), |v, v1| (v.0 + v1.0, v.1 + v1.1));
-    check_result!(context, [result.0, result.1], [405, 33780, 400, 23479]);
+        .try_fold((0, 0), |v, v1| v1.map(|v1| (v.0 + v1.0, v.1 + v1.1)))?;
+    check_result!(context, [result.0, result.1])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days;
+    use crate::utils::Dataset;
+
+    fn solve(lines: &[String]) -> Result<String, String> {
+        match days::get(13).unwrap().solve(&Dataset::Real, &lines.to_vec()) {
+            Ok(answer) => Ok(format!("{} {}", answer.part1.unwrap().value, answer.part2.unwrap().value)),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    /// Square pattern mirrored after its first row
+    fn pattern(size: usize) -> Vec<String> {
+        (0..size).map(|y| if y < 2 { ".".repeat(size) } else { format!("#{}", ".".repeat(size - 1)) }).collect()
+    }
+
+    #[test]
+    fn patterns_up_to_31_cells() {
+        let result = solve(&pattern(31)).unwrap();
+        assert!(result.starts_with("100 "), "{}", result);
+    }
+
+    /// The input found by the fuzzer (`fuzz/regressions/day13/`) overflowed with a first row of 35 cells
+    #[test]
+    fn larger_patterns_are_rejected() {
+        let error = solve(&pattern(32)).unwrap_err();
+        assert!(error.contains("pattern larger than 31 cells"), "{}", error);
+    }
 }
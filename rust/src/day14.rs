@@ -1,7 +1,9 @@
 use rustc_hash::FxHashMap;
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::ParseError,
     map2d::{Direction, Map2D, Pos},
     utils::Context,
 };
@@ -68,35 +70,29 @@ impl World {
     }
 }
 
-fn parse(lines: &Vec<String>) -> World {
-    let mut nb_rocks_per_row: Vec<u32> = vec![0; lines.len()];
-    let mut nb_rocks_per_column: Vec<u32> = vec![0; lines[0].len()];
-    let content = lines
-        .iter()
-        .enumerate()
-        .map(|(y, l)| {
-            l.chars()
-                .enumerate()
-                .map(|(x, c_str)| {
-                    let c: CellType = match c_str {
-                        '#' => CellType::SOLID,
-                        'O' => CellType::ROCK_,
-                        _ => CellType::EMPTY,
-                    };
-                    if c == CellType::ROCK_ {
-                        nb_rocks_per_row[y] += 1;
-                        nb_rocks_per_column[x] += 1;
-                    }
-                    return c;
-                })
-                .collect::<Vec<CellType>>()
-        })
-        .collect::<Vec<Vec<CellType>>>();
-    return World {
-        map:Map2D::new(content),
+fn parse(lines: &Vec<String>) -> Result<World, ParseError> {
+    let (map, rocks) = Map2D::try_from_lines_marked(
+        lines,
+        |c, _| {
+            Ok(match c {
+                '#' => CellType::SOLID,
+                'O' => CellType::ROCK_,
+                _ => CellType::EMPTY,
+            })
+        },
+        |cell| *cell == CellType::ROCK_,
+    )?;
+    let mut nb_rocks_per_row: Vec<u32> = vec![0; map.height()];
+    let mut nb_rocks_per_column: Vec<u32> = vec![0; map.width()];
+    for rock in rocks {
+        nb_rocks_per_row[rock.y] += 1;
+        nb_rocks_per_column[rock.x] += 1;
+    }
+    return Ok(World {
+        map,
         nb_rocks_per_column,
         nb_rocks_per_row,
-    };
+    });
 }
 
 fn run_cycle(map: &mut World, cycle: &mut u32, result_part_1: &mut u32) {
@@ -109,8 +105,8 @@ fn run_cycle(map: &mut World, cycle: &mut u32, result_part_1: &mut u32) {
     *cycle += 1;
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let mut map = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let mut map = context.phase("parse", || parse(lines))?;
     let mut cycle = 0;
     let mut result_part_1 = 0;
     let mut history_map: FxHashMap<(u32, u32), u32> = FxHashMap::default();
@@ -128,5 +124,5 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
             history_map.insert(key, cycle);
         }
     }
-    check_result!(context, [result_part_1, map.calc_load_up()], [136, 108955, 64, 106689]);
+    check_result!(context, [result_part_1, map.calc_load_up()])
 }
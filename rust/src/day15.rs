@@ -1,19 +1,21 @@
 use std::collections::BTreeMap;
 
 use crate::{
+    answers::Answer,
     check_result,
+    error::{InputLine, ParseError},
     utils::{Context, Part},
 };
 
 fn calc_hash(str: &str) -> u8 {
-    str.chars().fold(0 as u16, |h, c| ((h + c as u16) * 17) % 256) as u8
+    str.chars().fold(0 as u32, |h, c| ((h + c as u32) * 17) % 256) as u8
 }
 
 type BoxesMap<'a> = BTreeMap<u8, Vec<(&'a str, u8)>>;
 
-fn update_box<'a>(part:&'a str,boxes :&mut BoxesMap<'a>){
-    let op_pos = part.find(|c| c == '=' || c == '-').unwrap();
-            let op = part.chars().nth(op_pos).unwrap();
+fn update_box<'a>(line: &InputLine<'a>, part:&'a str,boxes :&mut BoxesMap<'a>) -> Result<(), ParseError> {
+    let op_pos = part.find(|c| c == '=' || c == '-').ok_or_else(|| line.error(part, "missing operation in"))?;
+            let op = part.as_bytes()[op_pos] as char;
             let lens = &part[0..op_pos];
             let box_id = calc_hash(lens);
             if !boxes.contains_key(&box_id) {
@@ -24,7 +26,7 @@ fn update_box<'a>(part:&'a str,boxes :&mut BoxesMap<'a>){
 
             match op {
                 '=' => {
-                    let value = part[op_pos + 1..].parse::<u8>().unwrap();
+                    let value = line.number::<u8>(&part[op_pos + 1..])?;
                     let found = box_found.iter_mut().enumerate().find(|existing_lens| existing_lens.1 .0 == lens);
                     if let Some(existing) = found {
                         existing.1 .1 = value;
@@ -34,18 +36,20 @@ fn update_box<'a>(part:&'a str,boxes :&mut BoxesMap<'a>){
                 }
                 _ => box_found.retain(|curr| curr.0 != lens),
             }
+            Ok(())
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let items = lines[0].split(",");
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let line = InputLine::get(lines, 0)?;
+    let items = line.text().split(",");
     if context.is_part(Part::Part1) {
-        let result = items.map(|part| calc_hash(part) as u32).sum();
-        check_result!(context, result, [1320, 511215]);
+        let result: u32 = items.map(|part| calc_hash(part) as u32).sum();
+        check_result!(context, result)
     } else {
         let mut boxes: BoxesMap = BTreeMap::new();
-        items.for_each(|part| {
-            update_box(part, &mut boxes)
-        });
+        for part in items {
+            update_box(&line, part, &mut boxes)?;
+        }
 
         let result: u32 = boxes
             .iter()
@@ -57,6 +61,6 @@ pub fn puzzle(context: &Context, lines: &Vec<String>) {
                     .sum::<u32>()
             })
             .sum();
-        check_result!(context, result, [145, 236057]);
+        check_result!(context, result)
     }
 }
@@ -3,7 +3,10 @@ use std::hash::Hasher;
 use rustc_hash::FxHashSet;
 
 use crate::{
-    check_result, log,
+    answers::Answer,
+    check_result,
+    error::ParseError,
+    log,
     map2d::{Direction, Map2D, Pos},
     utils::Context,
 };
@@ -18,15 +21,15 @@ enum CellType {
 }
 
 impl CellType {
-    fn from_char(c: char) -> CellType {
-        match c {
+    fn from_char(c: char) -> Option<CellType> {
+        Some(match c {
             '/' => CellType::MirrorSlash,
             '\\' => CellType::MirrorBackslash,
             '-' => CellType::SplitterHorizontal,
             '|' => CellType::SplitterVertical,
             '.' => CellType::Empty,
-            _ => panic!("Cannot map {}", c),
-        }
+            _ => return None,
+        })
     }
 
     fn next_dirs(&self, orig_dir: &Direction) -> Vec<Direction> {
@@ -190,25 +193,18 @@ impl Ray {
 
 type InputMap = Map2D<Cell>;
 
-fn parse(lines: &Vec<String>) -> InputMap {
-    let content = lines
-        .iter()
-        .map(|l| {
-            l.chars()
-                .map(|c| Cell {
-                    c_type: CellType::from_char(c),
-                    potential_rays: [None; 4],
-                })
-                .collect::<Vec<Cell>>()
+fn parse(lines: &Vec<String>) -> Result<InputMap, ParseError> {
+    let mut map = Map2D::try_from_lines(lines, |c, _| {
+        Ok(Cell {
+            c_type: CellType::from_char(c).ok_or("unknown cell type")?,
+            potential_rays: [None; 4],
         })
-        .collect::<Vec<Vec<Cell>>>();
-
-    let mut map = Map2D::new(content);
+    })?;
     for any_pos in map.iter_all_fast() {
         let rays = map.get(&any_pos).build_applicable_rays(&map, &any_pos);
         map.get_mut(&any_pos).potential_rays = rays;
     }
-    return map;
+    return Ok(map);
 }
 
 struct Solution<'a> {
@@ -256,7 +252,11 @@ fn solve(map: &InputMap, start_pos: Pos, dir: &Direction, context: &Context) ->
     let mut next_to_process: Vec<&Ray> = vec![];
     let start_cell = map.get(&start_pos);
     match start_cell.c_type {
-        CellType::Empty => next_to_process.push(start_cell.get_potential_ray(dir).unwrap()),
+        CellType::Empty => match start_cell.get_potential_ray(dir) {
+            Some(ray) => next_to_process.push(ray),
+            // the map is a single cell wide in that direction, the beam leaves it right away
+            None => return 1,
+        },
         _ => next_to_process.append(&mut non_empty_cell_next_dirs(start_cell, dir)),
     }
 
@@ -284,23 +284,26 @@ fn solve(map: &InputMap, start_pos: Pos, dir: &Direction, context: &Context) ->
     return resulting_marked.iter_all_fast().filter(|pos| *resulting_marked.get(pos) > 0).count();
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let map = parse(lines);
-    let result_part1 = solve(&map, Pos { x: 0, y: 0 }, &Direction::RIGHT, context);
-    let mut max_result = result_part1;
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let map = context.phase("parse", || parse(lines))?;
+    let result_part1 = context.phase("part1", || solve(&map, Pos { x: 0, y: 0 }, &Direction::RIGHT, context));
+    let max_result = context.phase("part2", || {
+        let mut max_result = result_part1;
 
-    for x in 0..map.width() {
-        if x != 0 {
-            max_result = max_result.max(solve(&map, Pos { x, y: 0 }, &Direction::DOWN, context));
+        for x in 0..map.width() {
+            if x != 0 {
+                max_result = max_result.max(solve(&map, Pos { x, y: 0 }, &Direction::DOWN, context));
+            }
+            max_result = max_result.max(solve(&map, Pos { x, y: map.height() - 1 }, &Direction::UP, context));
         }
-        max_result = max_result.max(solve(&map, Pos { x, y: map.height()-1 }, &Direction::UP, context));
-    }
 
-    for y in 1..map.height()-1 {    
-        max_result = max_result.max(solve(&map, Pos { x:0, y }, &Direction::RIGHT, context));
-        max_result = max_result.max(solve(&map, Pos { x:map.width()-1, y }, &Direction::LEFT, context));
-    }
+        for y in 1..map.height() - 1 {
+            max_result = max_result.max(solve(&map, Pos { x: 0, y }, &Direction::RIGHT, context));
+            max_result = max_result.max(solve(&map, Pos { x: map.width() - 1, y }, &Direction::LEFT, context));
+        }
 
+        max_result
+    });
 
-    check_result!(context, [result_part1, max_result], [46, 7951, 51, 8148]);
+    check_result!(context, [result_part1, max_result])
 }
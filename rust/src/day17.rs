@@ -1,18 +1,16 @@
 use crate::{
-    check_result, log,
+    answers::Answer,
+    check_result,
+    error::ParseError,
+    log,
     map2d::{Direction, Map2D, Pos},
     priority_queue::{Cost, Key, PriorityQueue},
     utils::{Context, Part},
 };
 
 type Input = Map2D<u8>;
-fn parse(lines: &Vec<String>) -> Input {
-    return Map2D::new(
-        lines
-            .iter()
-            .map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as u8).collect())
-            .collect::<Vec<Vec<u8>>>(),
-    );
+fn parse(lines: &Vec<String>) -> Result<Input, ParseError> {
+    return Map2D::try_from_lines(lines, |c, _| c.to_digit(10).map(|d| d as u8).ok_or("invalid digit"));
 }
 
 #[derive(Hash, PartialEq, Eq, Clone, Copy)]
@@ -99,7 +97,7 @@ fn next_possible_states<'a>(state: &State<'a>, map: &Input, min_step: usize, max
         .collect();
 }
 
-fn find_path<'a>(map: &Input, global: &'a StateGlobalInfo, is_part2: bool, is_debug: bool) -> State<'a> {
+fn find_path<'a>(map: &Input, global: &'a StateGlobalInfo, is_part2: bool, is_debug: bool) -> Option<State<'a>> {
     let min_step = if is_part2 { 4 } else { 1 };
     let max_step = if is_part2 { 10 } else { 3 };
 
@@ -126,7 +124,7 @@ fn find_path<'a>(map: &Input, global: &'a StateGlobalInfo, is_part2: bool, is_de
 
     while let Some(curr_state) = p_queue.pop() {
         if curr_state.last_pos == last_pos {
-            return curr_state;
+            return Some(curr_state);
         }
 
         for s in next_possible_states(&curr_state, map, min_step, max_step) {
@@ -134,22 +132,23 @@ fn find_path<'a>(map: &Input, global: &'a StateGlobalInfo, is_part2: bool, is_de
         }
     }
 
-    panic!("Not found path");
+    None
 }
 
-pub fn puzzle(context: &Context, lines: &Vec<String>) {
-    let map = parse(lines);
+pub fn puzzle(context: &Context, lines: &Vec<String>) -> Result<Answer, ParseError> {
+    let map = context.phase("parse", || parse(lines))?;
     let global = StateGlobalInfo {
         width: map.width() as u16,
         height: map.height() as u16,
         is_part_two: context.is_part(Part::Part2),
     };
 
-    let found_state = find_path(&map, &global, context.is_part(Part::Part2), context.is_debug());
+    let found_state = find_path(&map, &global, context.is_part(Part::Part2), context.is_debug())
+        .ok_or_else(|| ParseError::input("no path to the bottom right corner"))?;
     if context.is_part(Part::Part1) {
-        check_result!(context, found_state.heat_loss, [102, 1044]);
+        check_result!(context, found_state.heat_loss)
     } else {
         log!(debug, context, "Found path \n{:?}", found_state.history);
-        check_result!(context, found_state.heat_loss, [94, 1227]);
+        check_result!(context, found_state.heat_loss)
     }
 }
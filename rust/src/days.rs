@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use crate::answers::Answer;
+use crate::error::{ParseError, PuzzleError};
+use crate::pool;
+use crate::utils::{self, Context, Dataset, Part, RunOption};
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13, day14, day15, day16, day17};
+
+/// Sources of the puzzles, watched and generated by the runner
+pub const SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+pub type Puzzle = fn(&Context, &Vec<String>) -> Result<Answer, ParseError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunKind {
+    /// The puzzle is called once per part (`check_result!(context, result)`)
+    PerPart,
+    /// The puzzle solves both parts in a single call (`check_result!(context, [p1, p2])`)
+    Simultaneous,
+}
+
+pub struct DayDef {
+    pub day: u8,
+    pub title: &'static str,
+    pub puzzle: Puzzle,
+    pub kind: RunKind,
+}
+
+impl DayDef {
+    const fn per_part(day: u8, title: &'static str, puzzle: Puzzle) -> DayDef {
+        DayDef {
+            day,
+            title,
+            puzzle,
+            kind: RunKind::PerPart,
+        }
+    }
+
+    const fn simult(day: u8, title: &'static str, puzzle: Puzzle) -> DayDef {
+        DayDef {
+            day,
+            title,
+            puzzle,
+            kind: RunKind::Simultaneous,
+        }
+    }
+
+    /// Solves both parts on the lines of an input, without reading, checking nor logging anything but errors
+    pub fn solve(&self, data_set: &Dataset, lines: &Vec<String>) -> Result<Answer, PuzzleError> {
+        match self.kind {
+            RunKind::PerPart => Ok(self.solve_part(Part::Part1, data_set, lines)?.merge(self.solve_part(Part::Part2, data_set, lines)?)),
+            RunKind::Simultaneous => Context::solver(self.day, None, data_set).call(&self.puzzle, lines),
+        }
+    }
+
+    /// Solves a part, the answer of a day solving both parts at once having the other one too
+    pub fn solve_part(&self, part: Part, data_set: &Dataset, lines: &Vec<String>) -> Result<Answer, PuzzleError> {
+        match self.kind {
+            RunKind::PerPart => Context::solver(self.day, Some(part), data_set).call(&self.puzzle, lines),
+            RunKind::Simultaneous => Context::solver(self.day, None, data_set).call(&self.puzzle, lines),
+        }
+    }
+
+    /// Solves each part on its own, a part failing not preventing the other one from being solved.
+    /// A day solving both parts at once is only called once.
+    pub fn solve_parts(&self, data_set: &Dataset, lines: &Vec<String>) -> Vec<Result<Answer, PuzzleError>> {
+        match self.kind {
+            RunKind::PerPart => [Part::Part1, Part::Part2].into_iter().map(|part| self.solve_part(part, data_set, lines)).collect(),
+            RunKind::Simultaneous => vec![self.solve(data_set, lines)],
+        }
+    }
+
+    pub fn run(&self, options: RunOption) -> Result<Vec<(Dataset, Answer)>, PuzzleError> {
+        match self.kind {
+            RunKind::PerPart => utils::run_all(&self.day, &self.puzzle, options),
+            RunKind::Simultaneous => utils::run_all_simult(&self.day, &self.puzzle, options),
+        }
+    }
+}
+
+/// Runs the days in order, their jobs being spread over a thread pool when the options ask for a parallel run
+pub fn run_days(days: &[DayDef], options: RunOption) {
+    // failures are reported and counted by the runner
+    let Some(threads) = options.get_threads() else {
+        days.iter().for_each(|def| {
+            let _ = def.run(options);
+        });
+        return;
+    };
+    let jobs: Vec<(&DayDef, utils::Job)> = days
+        .iter()
+        .flat_map(|def| options.get_jobs(&def.day, def.kind == RunKind::PerPart).into_iter().map(move |job| (def, job)))
+        .collect();
+    let mut outcomes = pool::map(&jobs, threads, |(def, job)| utils::run_job(job, &def.puzzle, &options, true))
+        .into_iter()
+        .peekable();
+    for def in days.iter().filter(|def| options.is_active(&def.day)) {
+        let day_outcomes = std::iter::from_fn(|| outcomes.next_if(|outcome| outcome.job.day == def.day));
+        let _ = utils::report_day(&def.day, def.kind == RunKind::PerPart, day_outcomes, &options);
+    }
+}
+
+pub const DAYS: &[DayDef] = &[
+    DayDef::per_part(1, "Trebuchet?!", day01::puzzle),
+    DayDef::per_part(2, "Cube Conundrum", day02::puzzle),
+    DayDef::per_part(3, "Gear Ratios", day03::puzzle),
+    DayDef::per_part(4, "Scratchcards", day04::puzzle),
+    DayDef::per_part(5, "If You Give A Seed A Fertilizer", day05::puzzle),
+    DayDef::per_part(6, "Wait For It", day06::puzzle),
+    DayDef::per_part(7, "Camel Cards", day07::puzzle),
+    DayDef::simult(8, "Haunted Wasteland", day08::puzzle),
+    DayDef::simult(9, "Mirage Maintenance", day09::puzzle),
+    DayDef::simult(10, "Pipe Maze", day10::puzzle),
+    DayDef::per_part(11, "Cosmic Expansion", day11::puzzle),
+    DayDef::per_part(12, "Hot Springs", day12::puzzle),
+    DayDef::simult(13, "Point of Incidence", day13::puzzle),
+    DayDef::simult(14, "Parabolic Reflector Dish", day14::puzzle),
+    DayDef::per_part(15, "Lens Library", day15::puzzle),
+    DayDef::simult(16, "The Floor Will Be Lava", day16::puzzle),
+    DayDef::per_part(17, "Clumsy Crucible", day17::puzzle),
+];
+
+pub fn get(day: u8) -> Option<&'static DayDef> {
+    DAYS.iter().find(|def| def.day == day)
+}
+
+/// Checks that every registered day is in 1..=25, registered only once, and that no day is missing in between
+pub fn validate(days: &[DayDef]) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+    let mut registered = BTreeSet::new();
+    for def in days {
+        if !(1..=25).contains(&def.day) {
+            errors.push(format!("Day {} ({}) is not a valid day", def.day, def.title));
+        } else if !registered.insert(def.day) {
+            errors.push(format!("Day {} ({}) is registered more than once", def.day, def.title));
+        }
+    }
+    if let Some(last) = registered.last() {
+        (1..*last)
+            .filter(|day| !registered.contains(day))
+            .for_each(|day| errors.push(format!("Day {} is missing from the registry", day)));
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
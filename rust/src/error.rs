@@ -0,0 +1,216 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::panics::PanicReport;
+use crate::utils::{Dataset, Part};
+
+/// Invalid content found while parsing a puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number, 0 when the input as a whole is invalid
+    pub line: usize,
+    /// 1-based column, 0 when the whole line is invalid
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line_index: usize, column: usize, text: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: line_index + 1,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error about the input as a whole
+    pub fn input(message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: 0,
+            column: 0,
+            text: String::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn empty_input() -> ParseError {
+        ParseError::input("empty input")
+    }
+
+    pub fn missing_line(line_index: usize) -> ParseError {
+        ParseError::new(line_index, 0, "", "missing line")
+    }
+
+    /// A value computed from the input doesn't fit in the type of the answer
+    pub fn overflow() -> ParseError {
+        ParseError::input("numbers of the input too large, the computation overflows")
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (0, _) => write!(f, "{}", self.message),
+            (line, 0) => write!(f, "line {}: {} in '{}'", line, self.message, self.text),
+            (line, column) => write!(f, "line {}, column {}: {} '{}'", line, column, self.message, self.text),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of the input, giving the position of the errors found in it
+#[derive(Debug, Clone, Copy)]
+pub struct InputLine<'a> {
+    index: usize,
+    text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    pub fn new(index: usize, text: &'a str) -> InputLine<'a> {
+        InputLine { index, text }
+    }
+
+    pub fn get(lines: &'a [String], index: usize) -> Result<InputLine<'a>, ParseError> {
+        lines
+            .get(index)
+            .map(|text| InputLine::new(index, text))
+            .ok_or_else(|| ParseError::missing_line(index))
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// 1-based column of `token` when it is a slice of the line, 0 otherwise
+    fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        if token_start >= start && token_start + token.len() <= start + self.text.len() {
+            token_start - start + 1
+        } else {
+            0
+        }
+    }
+
+    /// Error pointing at `token`, a slice of the line
+    pub fn error(&self, token: &str, message: impl Into<String>) -> ParseError {
+        match self.column(token) {
+            0 => ParseError::new(self.index, 0, self.text, message),
+            column => ParseError::new(self.index, column, token, message),
+        }
+    }
+
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        token.trim().parse::<T>().map_err(|_| self.error(token, "invalid number"))
+    }
+
+    pub fn split_once(&self, token: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+        token
+            .split_once(separator)
+            .ok_or_else(|| self.error(token, format!("missing '{}' in", separator)))
+    }
+}
+
+/// Width of a grid input in characters, checking that it isn't empty and that every line has the same width
+pub fn grid_width(lines: &[String]) -> Result<usize, ParseError> {
+    let width = lines.first().ok_or_else(ParseError::empty_input)?.chars().count();
+    if width == 0 {
+        return Err(ParseError::new(0, 0, "", "empty row"));
+    }
+    match lines.iter().enumerate().find(|(_, line)| line.chars().count() != width) {
+        Some((index, line)) => Err(InputLine::new(index, line).error(line, format!("width isn't {} in", width))),
+        None => Ok(width),
+    }
+}
+
+/// Arithmetic on the values of an input, failing instead of overflowing
+pub trait Checked: Sized {
+    fn add_or_err(self, other: Self) -> Result<Self, ParseError>;
+    fn sub_or_err(self, other: Self) -> Result<Self, ParseError>;
+    fn mul_or_err(self, other: Self) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_checked {
+    ($($type:ty),*) => {
+        $(
+            impl Checked for $type {
+                fn add_or_err(self, other: Self) -> Result<Self, ParseError> {
+                    self.checked_add(other).ok_or_else(ParseError::overflow)
+                }
+
+                fn sub_or_err(self, other: Self) -> Result<Self, ParseError> {
+                    self.checked_sub(other).ok_or_else(ParseError::overflow)
+                }
+
+                fn mul_or_err(self, other: Self) -> Result<Self, ParseError> {
+                    self.checked_mul(other).ok_or_else(ParseError::overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked!(u32, u64, usize, i32, i64);
+
+/// Product of the values, failing instead of overflowing
+pub fn checked_product<T: Checked + From<u8>>(values: impl IntoIterator<Item = T>) -> Result<T, ParseError> {
+    values.into_iter().try_fold(T::from(1), T::mul_or_err)
+}
+
+/// Failure of a day, reported by the runner before going on with the other days
+#[derive(Debug)]
+pub enum PuzzleError {
+    MissingInput {
+        day: u8,
+        data_set: Dataset,
+        dir: PathBuf,
+    },
+    Io {
+        day: u8,
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        day: u8,
+        data_set: Dataset,
+        source: ParseError,
+    },
+    Panic {
+        day: u8,
+        part: Option<Part>,
+        data_set: Dataset,
+        report: PanicReport,
+    },
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleError::MissingInput { day, data_set, dir } => {
+                write!(f, "no {1} input file found for day {0} in {2}", day, data_set, dir.display())
+            }
+            PuzzleError::Io { day, path, source } => write!(f, "cannot read {} of day {}: {}", path.display(), day, source),
+            PuzzleError::Parse { day, data_set, source } => write!(f, "invalid {} input of day {}: {}", data_set, day, source),
+            PuzzleError::Panic { day, part, data_set, report } => match part {
+                Some(part) => write!(f, "day {} panicked on {:?} of {}: {}", day, part, data_set, report),
+                None => write!(f, "day {} panicked on {}: {}", day, data_set, report),
+            },
+        }
+    }
+}
+
+impl Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PuzzleError::MissingInput { .. } | PuzzleError::Panic { .. } => None,
+            PuzzleError::Io { source, .. } => Some(source),
+            PuzzleError::Parse { source, .. } => Some(source),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::{Dataset, Part};
+
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+
+/// Tried in order when neither the command line nor the environment gives the data directory
+const DEFAULT_DATA_DIRS: [&str; 3] = ["data", "../data", concat!(env!("CARGO_MANIFEST_DIR"), "/../data")];
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+/// Supported layouts of the input files inside the data directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingScheme {
+    /// `day_1.dat`, `day_1_test.dat`, `day_1_2_test.dat`
+    Legacy,
+    /// `day01.txt`, `day01_test.txt`, `day01_2_test.txt`
+    Padded,
+    /// `inputs/2023/01/input.txt`, `inputs/2023/01/test.txt`, `inputs/2023/01/test_2.txt`
+    Tree,
+}
+
+impl NamingScheme {
+    pub const ALL: [NamingScheme; 3] = [NamingScheme::Legacy, NamingScheme::Padded, NamingScheme::Tree];
+
+    /// `variant` is either a part number or the name of an example
+    fn relative_path(&self, day: u8, variant: Option<&str>, is_test: bool) -> PathBuf {
+        let variant = variant.map(|v| format!("_{}", v)).unwrap_or_default();
+        let test_suffix = if is_test { "_test" } else { "" };
+        match self {
+            NamingScheme::Legacy => PathBuf::from(format!("day_{}{}{}.dat", day, variant, test_suffix)),
+            NamingScheme::Padded => PathBuf::from(format!("day{:02}{}{}.txt", day, variant, test_suffix)),
+            NamingScheme::Tree => Path::new("inputs")
+                .join("2023")
+                .join(format!("{:02}", day))
+                .join(format!("{}{}.txt", if is_test { "test" } else { "input" }, variant)),
+        }
+    }
+
+    /// Extracts the example name from a test file name of the given day, if it is one
+    fn example_name(&self, day: u8, file_name: &str) -> Option<String> {
+        let name = match self {
+            NamingScheme::Legacy => file_name.strip_prefix(&format!("day_{}_", day))?.strip_suffix("_test.dat")?,
+            NamingScheme::Padded => file_name.strip_prefix(&format!("day{:02}_", day))?.strip_suffix("_test.txt")?,
+            NamingScheme::Tree => file_name.strip_prefix("test_")?.strip_suffix(".txt")?,
+        };
+        Some(name.to_string()).filter(|name| !name.is_empty())
+    }
+
+    fn directory(&self, day: u8) -> PathBuf {
+        match self {
+            NamingScheme::Legacy | NamingScheme::Padded => PathBuf::new(),
+            NamingScheme::Tree => Path::new("inputs").join("2023").join(format!("{:02}", day)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    pub fn new(root: impl Into<PathBuf>) -> DataDir {
+        DataDir { root: root.into() }
+    }
+
+    /// Uses the given directory, then the `AOC_DATA_DIR` environment variable, then the first existing default directory
+    pub fn resolve(from_cli: Option<&str>) -> DataDir {
+        if let Some(dir) = from_cli {
+            return DataDir::new(dir);
+        }
+        if let Some(dir) = std::env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            return DataDir::new(dir);
+        }
+        let root = DEFAULT_DATA_DIRS
+            .iter()
+            .map(Path::new)
+            .find(|dir| dir.is_dir())
+            .unwrap_or(Path::new(DEFAULT_DATA_DIRS[1]));
+        DataDir::new(root)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn answers_file(&self) -> PathBuf {
+        self.root.join(ANSWERS_FILE)
+    }
+
+    /// Paths that are tried, by order of preference, to find an input
+    pub fn candidates(&self, day: u8, part: Option<Part>, data_set: &Dataset) -> Vec<PathBuf> {
+        let is_test = data_set.is_test();
+        let part_variant = part.map(|p| match p {
+            Part::Part1 => "1",
+            Part::Part2 => "2",
+        });
+        let variants: Vec<Option<&str>> = match data_set {
+            Dataset::Test => vec![None],
+            Dataset::Example(name) => vec![Some(name.as_str())],
+            Dataset::Real => part_variant.into_iter().map(Some).chain([None]).collect(),
+        };
+        variants
+            .iter()
+            .flat_map(|variant| NamingScheme::ALL.iter().map(move |scheme| self.root.join(scheme.relative_path(day, *variant, is_test))))
+            .collect()
+    }
+
+    pub fn find_input(&self, day: u8, part: Option<Part>, data_set: &Dataset) -> Option<PathBuf> {
+        self.candidates(day, part, data_set).into_iter().find(|path| path.is_file())
+    }
+
+    /// Every path an input of the day may be read from, existing or not
+    pub fn input_files(&self, day: u8) -> Vec<PathBuf> {
+        let mut data_sets = vec![(None, Dataset::Test), (Some(Part::Part1), Dataset::Real), (Some(Part::Part2), Dataset::Real)];
+        data_sets.extend(self.examples(day).into_iter().map(|name| (None, Dataset::Example(name))));
+        let mut paths: Vec<PathBuf> = data_sets.iter().flat_map(|(part, data_set)| self.candidates(day, *part, data_set)).collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// Whether the part has its own example (e.g. `day_1_2_test.dat`), checked instead of the default one
+    pub fn has_part_example(&self, day: u8, part: Part) -> bool {
+        self.examples(day).into_iter().any(|name| Dataset::Example(name).only_part() == Some(part))
+    }
+
+    /// Names of the additional examples of a day, whatever their naming scheme
+    pub fn examples(&self, day: u8) -> Vec<String> {
+        let mut names: Vec<String> = NamingScheme::ALL
+            .iter()
+            .flat_map(|scheme| {
+                fs::read_dir(self.root.join(scheme.directory(day)))
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|entry| entry.ok())
+                    .filter_map(move |entry| scheme.example_name(day, &entry.file_name().to_string_lossy()))
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
@@ -0,0 +1,36 @@
+//! Solvers of the Advent of Code 2023 puzzles, with the helpers they share and the runner that checks and times them.
+//! A day is solved with `days::get(day)`, then `DayDef::solve` on the lines of an input.
+
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod days;
+pub mod error;
+pub mod inputs;
+pub mod logger;
+pub mod output;
+pub mod panics;
+pub mod pool;
+pub mod report;
+pub mod utils;
+pub mod priority_queue;
+pub mod map2d;
+
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
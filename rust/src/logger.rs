@@ -0,0 +1,126 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use crate::output;
+use crate::report::json_string;
+use crate::utils::{Dataset, LogLevel, Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `[Day 4/Part1/Test]message`
+    #[default]
+    Text,
+    /// One JSON object per line, with the level, day, part and dataset as fields
+    Json,
+}
+
+enum Sink {
+    /// Goes through the run output, so that the logs of parallel jobs are grouped per job
+    Stdout,
+    Stderr,
+    File(File),
+}
+
+/// Where and how the run contexts write their logs, each record being written as a single line
+pub struct Logger {
+    format: LogFormat,
+    sink: Mutex<Sink>,
+}
+
+/// A log message of a run context
+pub struct Record<'a> {
+    pub level: &'a LogLevel,
+    pub day: u8,
+    pub part: Option<Part>,
+    pub data_set: &'a Dataset,
+    pub message: &'a str,
+}
+
+static LOGGER: OnceLock<Logger> = OnceLock::new();
+
+fn level_name(level: &LogLevel) -> &'static str {
+    match level {
+        LogLevel::ERROR => "error",
+        LogLevel::WARN => "warn",
+        LogLevel::INFO => "info",
+        LogLevel::DEBUG => "debug",
+        LogLevel::TRACE => "trace",
+    }
+}
+
+impl Logger {
+    fn new(format: LogFormat, sink: Sink) -> Logger {
+        Logger {
+            format,
+            sink: Mutex::new(sink),
+        }
+    }
+
+    pub fn stdout(format: LogFormat) -> Logger {
+        Logger::new(format, Sink::Stdout)
+    }
+
+    pub fn stderr(format: LogFormat) -> Logger {
+        Logger::new(format, Sink::Stderr)
+    }
+
+    /// Logs appended to the file, which is created if needed
+    pub fn file(format: LogFormat, path: &Path) -> Result<Logger, String> {
+        File::options()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map(|file| Logger::new(format, Sink::File(file)))
+            .map_err(|e| format!("Cannot open {}: {}", path.display(), e))
+    }
+
+    /// Makes the logger used by every run context
+    pub fn install(self) {
+        if LOGGER.set(self).is_err() {
+            panic!("Logger already installed");
+        }
+    }
+
+    /// The installed logger, or a text logger on the standard output
+    pub fn installed() -> &'static Logger {
+        LOGGER.get_or_init(|| Logger::stdout(LogFormat::Text))
+    }
+
+    fn format(&self, record: &Record) -> String {
+        match self.format {
+            LogFormat::Text => match record.part {
+                Some(part) => format!("[Day {}/{:?}/{}]{}", record.day, part, record.data_set, record.message),
+                None => format!("[Day {}/ALL/{}]{}", record.day, record.data_set, record.message),
+            },
+            LogFormat::Json => format!(
+                "{{\"level\":\"{}\",\"day\":{},\"part\":{},\"dataset\":{},\"message\":{}}}",
+                level_name(record.level),
+                record.day,
+                match record.part {
+                    Some(Part::Part1) => "1",
+                    Some(Part::Part2) => "2",
+                    None => "null",
+                },
+                json_string(&record.data_set.name()),
+                json_string(record.message)
+            ),
+        }
+    }
+
+    pub fn log(&self, record: &Record) {
+        let line = self.format(record);
+        // a panic while writing (e.g. on a closed stdout) is caught with the puzzle one, the logger being still usable
+        let mut sink = self.sink.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // a log that can't be written isn't worth failing the run
+        let _ = match &mut *sink {
+            Sink::Stdout => {
+                output::write_line(format_args!("{}", line));
+                Ok(())
+            }
+            Sink::Stderr => writeln!(io::stderr().lock(), "{}", line),
+            Sink::File(file) => writeln!(file, "{}", line),
+        };
+    }
+}
@@ -1,61 +1,159 @@
+use std::path::Path;
 use std::time::Instant;
 
-use crate::utils::{RunOption, DaysRestriction,Context};
-#[allow(unused_imports)]
-use crate::utils::Part::{Part2,Part1};
-
-mod utils;
-mod priority_queue;
-mod map2d;
-
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-/*mod day14;
-mod day16;
-mod day19;
-mod day20;
-mod day23;
-mod day24;*/
+use advent_of_code_2023::answers::AnswersStore;
+use advent_of_code_2023::baseline::Baseline;
+use advent_of_code_2023::days::{self, RunKind};
+use advent_of_code_2023::inputs::DataDir;
+use advent_of_code_2023::report;
+use advent_of_code_2023::utils::{Context, Dataset};
+
+use crate::cli::Cli;
+
+mod cli;
+mod scaffold;
+mod watch;
 
 fn main() {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+
+    if let Err(errors) = days::validate(days::DAYS) {
+        errors.iter().for_each(|error| eprintln!("[Registry] {}", error));
+        std::process::exit(1);
+    }
+    let data_dir = DataDir::resolve(cli.data_dir.as_deref());
+    if let Some(day) = cli.new_day {
+        match scaffold::new_day(day, cli.title.as_deref(), &data_dir) {
+            Ok(done) => done.iter().for_each(|line| println!("[New day] {}", line)),
+            Err(message) => {
+                eprintln!("[New day] {}", message);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    match AnswersStore::load(&data_dir.answers_file()) {
+        Ok(store) => store.install(),
+        Err(message) => {
+            eprintln!("[Answers] {}", message);
+            std::process::exit(1);
+        }
+    }
+    match cli.logger() {
+        Ok(logger) => logger.install(),
+        Err(message) => {
+            eprintln!("[Log] {}", message);
+            std::process::exit(1);
+        }
+    }
+    if cli.list {
+        for def in days::DAYS {
+            let kind = match def.kind {
+                RunKind::PerPart => "per part",
+                RunKind::Simultaneous => "global",
+            };
+            println!("Day {:>2} [{:<8}] {}", def.day, kind, def.title);
+        }
+        return;
+    }
+    if let Some(requested) = &cli.days {
+        requested
+            .iter()
+            .filter(|day| days::get(**day).is_none())
+            .for_each(|day| eprintln!("[Day {}] not registered, skipping", day));
+    }
+    if let Some(Dataset::Example(example)) = &cli.dataset {
+        days::DAYS
+            .iter()
+            .filter(|def| cli.days.as_ref().is_none_or(|requested| requested.contains(&def.day)))
+            .map(|def| (def.day, data_dir.examples(def.day)))
+            .filter(|(_, examples)| !examples.contains(example))
+            .for_each(|(day, examples)| eprintln!("[Day {}] no example '{}' (available: {:?})", day, example, examples));
+    }
+
+    if cli.watch {
+        let options = cli.run_option(&data_dir);
+        let watched: Vec<&days::DayDef> = days::DAYS.iter().filter(|def| options.is_active(&def.day)).collect();
+        watch::watch(&watched, options, &data_dir);
+        return;
+    }
+
     let start = Instant::now();
-    //let days_restriction:DaysRestriction = &Some(vec![17]);
-    let days_restriction:DaysRestriction = &None;
-    
-    utils::run_all(&1, &day01::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&2, &day02::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&3, &day03::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&4, &day04::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&5, &day05::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&6, &day06::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&7, &day07::puzzle, RunOption::default(days_restriction));
-    utils::run_all_simult(&8, &day08::puzzle, RunOption::default(days_restriction));
-    utils::run_all_simult(&9, &day09::puzzle, RunOption::default(days_restriction));
-    utils::run_all_simult(&10, &day10::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&11, &day11::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&12, &day12::puzzle, RunOption::default(days_restriction));
-    utils::run_all_simult(&13, &day13::puzzle, RunOption::default(days_restriction));
-    utils::run_all_simult(&14, &day14::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&15, &day15::puzzle, RunOption::default(days_restriction));
-    utils::run_all_simult(&16, &day16::puzzle, RunOption::default(days_restriction));
-    utils::run_all(&17, &day17::puzzle, RunOption::default(days_restriction));
+    days::run_days(days::DAYS, cli.run_option(&data_dir));
     let duration = start.elapsed().as_millis() as u64;
+    if let Some(store) = AnswersStore::installed().filter(|store| store.is_modified()) {
+        match store.save() {
+            Ok(_) => println!("[Answers] recorded new answers in {}", store.path().display()),
+            Err(e) => eprintln!("[Answers] cannot save {}: {}", store.path().display(), e),
+        }
+    }
     println!("");
-    println!("[ALL] Overall finished in {} ms with {} errors",duration,Context::get_errors());
-}
\ No newline at end of file
+    println!(
+        "[ALL] Overall finished in {} ms with {} failures ({} errors, {} panics)",
+        duration,
+        Context::get_failures(),
+        Context::get_errors(),
+        Context::get_panics()
+    );
+    let reports = report::all();
+    if cli.bench.is_some() {
+        println!();
+        report::bench_summary(&reports).iter().for_each(|line| println!("[Bench] {}", line));
+    }
+    let nb_regressions = cli.baseline.as_ref().map_or(0, |path| compare_baseline(path, cli.threshold, &reports));
+    if let Some(path) = &cli.save_baseline {
+        match Baseline::from_reports(&reports).save(Path::new(path)) {
+            Ok(_) => println!("[Baseline] saved in {}", path),
+            Err(message) => eprintln!("[Baseline] {}", message),
+        }
+    }
+    write_report(&cli.json, || report::to_json(&reports, duration, Context::get_failures()));
+    write_report(&cli.junit, || report::to_junit(&reports, duration));
+    if Context::get_failures() > 0 || nb_regressions > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn compare_baseline(path: &str, threshold_pct: f64, reports: &[report::RunReport]) -> usize {
+    let baseline = match Baseline::load(Path::new(path)) {
+        Ok(baseline) => baseline,
+        Err(message) => {
+            eprintln!("[Baseline] {}", message);
+            std::process::exit(1);
+        }
+    };
+    let regressions = baseline.regressions(reports, threshold_pct);
+    for regression in &regressions {
+        println!(
+            "[Baseline] Day {} {} regressed: median {:.3} ms instead of {:.3} ms (+{:.1}%)",
+            regression.day,
+            regression.key,
+            regression.median_ms,
+            regression.baseline_ms,
+            regression.increase_pct()
+        );
+    }
+    if regressions.is_empty() {
+        println!("[Baseline] no regression above {}% against {}", threshold_pct, path);
+    }
+    regressions.len()
+}
+
+fn write_report(target: &Option<String>, content: impl FnOnce() -> String) {
+    if let Some(target) = target {
+        if let Err(e) = report::write(target, &content()) {
+            eprintln!("[Report] cannot write {}: {}", target, e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 use std::ops::{Add, RangeInclusive, Sub};
 
+use crate::error::{grid_width, InputLine, ParseError};
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Pos {
     pub x: usize,
@@ -45,14 +47,14 @@ impl Pos {
     pub fn move_multiple_pos(&self, dirs: &[Direction], height: usize, width: usize) -> Option<Pos> {
         let mut curr_pos = Pos { x: self.x, y: self.y };
         for dir in dirs {
-            if let Some(new_pos) = self.move_pos(dir, height, width) {
+            if let Some(new_pos) = curr_pos.move_pos(dir, height, width) {
                 curr_pos.x = new_pos.x;
                 curr_pos.y = new_pos.y;
             } else {
                 return None;
             }
         }
-        return None;
+        return Some(curr_pos);
     }
 
     pub fn move_pos_anydir(&self, dir_any: &DirectionAny, height: usize, width: usize) -> Option<Pos> {
@@ -152,6 +154,7 @@ pub struct Vec2D {
     pub y: isize,
 }
 
+#[derive(Debug, PartialEq, Eq)]
 pub enum TurnType {
     Strait,
     Opposite,
@@ -199,12 +202,50 @@ pub struct Map2D<T> {
 }
 
 impl<T> Map2D<T> {
+    /// Panics when the rows don't all have the same width, use `from_lines` for a map coming from an input
     pub fn new(content: Vec<Vec<T>>) -> Map2D<T> {
-        return Map2D {
+        let width = content.first().map_or(0, Vec::len);
+        assert!(content.iter().all(|row| row.len() == width), "Rows of a map must have the same width");
+        Map2D {
             height: content.len(),
-            width: content[0].len(),
-            content: content,
-        };
+            width,
+            content,
+        }
+    }
+
+    /// Map of the characters of a grid input, one row per line
+    pub fn from_lines(lines: &[String], mut cell: impl FnMut(char, Pos) -> T) -> Result<Map2D<T>, ParseError> {
+        Map2D::try_from_lines(lines, |c, pos| Ok(cell(c, pos)))
+    }
+
+    /// Map of the characters of a grid input, the error of a character being reported at its position in the input
+    pub fn try_from_lines(lines: &[String], cell: impl FnMut(char, Pos) -> Result<T, &'static str>) -> Result<Map2D<T>, ParseError> {
+        Ok(Map2D::try_from_lines_marked(lines, cell, |_| false)?.0)
+    }
+
+    /// Same as `try_from_lines`, also giving the positions of the cells that are marked, row by row
+    pub fn try_from_lines_marked(
+        lines: &[String],
+        mut cell: impl FnMut(char, Pos) -> Result<T, &'static str>,
+        is_marked: impl Fn(&T) -> bool,
+    ) -> Result<(Map2D<T>, Vec<Pos>), ParseError> {
+        let width = grid_width(lines)?;
+        let mut marked = vec![];
+        let mut content = Vec::with_capacity(lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let input_line = InputLine::new(y, line);
+            let mut row = Vec::with_capacity(width);
+            for (x, (index, c)) in line.char_indices().enumerate() {
+                let pos = Pos { x, y };
+                let value = cell(c, pos).map_err(|message| input_line.error(&line[index..index + c.len_utf8()], message))?;
+                if is_marked(&value) {
+                    marked.push(pos);
+                }
+                row.push(value);
+            }
+            content.push(row);
+        }
+        Ok((Map2D::new(content), marked))
     }
 
     pub fn get_content(&self)->&Vec<Vec<T>>{
@@ -232,7 +273,7 @@ impl<T> Map2D<T> {
     }
 
     pub fn is_valid_pos(&self, pos: &Pos) -> bool {
-        pos.x < self.width && pos.y < self.width
+        pos.x < self.width && pos.y < self.height
     }
 
     pub fn is_border(&self, pos: &Pos) -> bool {
@@ -421,7 +462,7 @@ impl IterAll {
 
     fn next_y(&self) -> Option<usize> {
         if let Some(new_y) = self.pos.y.checked_add_signed(self.delta_y) {
-            if (new_y < self.width) {
+            if (new_y < self.height) {
                 return Some(new_y);
             }
         }
@@ -462,3 +503,279 @@ impl Iterator for IterAll {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Map of 3 columns and 2 rows, each cell holding its own position
+    fn map_3x2() -> Map2D<(usize, usize)> {
+        Map2D::new((0..2).map(|y| (0..3).map(|x| (x, y)).collect()).collect())
+    }
+
+    fn pos(x: usize, y: usize) -> Pos {
+        Pos { x, y }
+    }
+
+    fn positions(coords: &[(usize, usize)]) -> Vec<Pos> {
+        coords.iter().map(|(x, y)| pos(*x, *y)).collect()
+    }
+
+    #[test]
+    fn pos_moves_inside_the_map() {
+        let center = pos(1, 1);
+        assert_eq!(center.move_pos(&Direction::UP, 3, 3), Some(pos(1, 0)));
+        assert_eq!(center.move_pos(&Direction::DOWN, 3, 3), Some(pos(1, 2)));
+        assert_eq!(center.move_pos(&Direction::LEFT, 3, 3), Some(pos(0, 1)));
+        assert_eq!(center.move_pos(&Direction::RIGHT, 3, 3), Some(pos(2, 1)));
+    }
+
+    #[test]
+    fn pos_doesnt_move_out_of_the_map() {
+        assert_eq!(pos(0, 0).move_pos(&Direction::UP, 2, 3), None);
+        assert_eq!(pos(0, 0).move_pos(&Direction::LEFT, 2, 3), None);
+        assert_eq!(pos(2, 1).move_pos(&Direction::DOWN, 2, 3), None);
+        assert_eq!(pos(2, 1).move_pos(&Direction::RIGHT, 2, 3), None);
+        assert_eq!(pos(2, 0).move_pos(&Direction::DOWN, 2, 3), Some(pos(2, 1)));
+        assert_eq!(pos(1, 1).move_pos(&Direction::RIGHT, 2, 3), Some(pos(2, 1)));
+    }
+
+    #[test]
+    fn pos_moves_multiple_times_from_the_moved_position() {
+        assert_eq!(pos(0, 0).move_multiple_pos(&[Direction::RIGHT, Direction::DOWN], 3, 3), Some(pos(1, 1)));
+        assert_eq!(pos(0, 0).move_multiple_pos(&[Direction::RIGHT, Direction::RIGHT], 3, 3), Some(pos(2, 0)));
+        assert_eq!(pos(0, 0).move_multiple_pos(&[Direction::RIGHT, Direction::RIGHT, Direction::RIGHT], 3, 3), None);
+        assert_eq!(pos(1, 1).move_multiple_pos(&[], 3, 3), Some(pos(1, 1)));
+    }
+
+    #[test]
+    fn pos_moves_in_any_direction() {
+        let center = pos(1, 1);
+        let expected = positions(&[(1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1), (0, 0)]);
+        let moved: Vec<Pos> = DirectionAny::ALL_DIRECTIONS_CLOCKWISE
+            .iter()
+            .map(|dir| center.move_pos_anydir(dir, 3, 3).unwrap())
+            .collect();
+        assert_eq!(moved, expected);
+    }
+
+    #[test]
+    fn pos_moves_diagonally_only_inside_the_map() {
+        let corner = pos(0, 0);
+        let moved: Vec<&DirectionAny> = DirectionAny::ALL_DIRECTIONS_CLOCKWISE
+            .iter()
+            .filter(|dir| corner.move_pos_anydir(dir, 2, 3).is_some())
+            .collect();
+        assert_eq!(moved, vec![&DirectionAny::RIGHT, &DirectionAny::DOWN_RIGHT, &DirectionAny::DOWN]);
+        assert_eq!(pos(2, 0).move_pos_anydir(&DirectionAny::DOWN_LEFT, 2, 3), Some(pos(1, 1)));
+        assert_eq!(pos(2, 1).move_pos_anydir(&DirectionAny::DOWN_LEFT, 2, 3), None);
+    }
+
+    #[test]
+    fn pos_turn_type() {
+        let center = pos(1, 1);
+        assert_eq!(center.calc_turn_type(&pos(0, 1), &pos(2, 1)), TurnType::Strait);
+        assert_eq!(center.calc_turn_type(&pos(0, 1), &pos(0, 1)), TurnType::Opposite);
+        assert_eq!(center.calc_turn_type(&pos(0, 1), &pos(1, 2)), TurnType::ClockWise(-1));
+        assert_eq!(center.calc_turn_type(&pos(0, 1), &pos(1, 0)), TurnType::CounterClockWise(1));
+    }
+
+    #[test]
+    fn direction_opposite() {
+        for dir in Direction::ALL_DIRECTIONS_CLOCKWISE {
+            assert_ne!(dir.opposite(), &dir);
+            assert_eq!(dir.opposite().opposite(), &dir);
+            assert_eq!(dir.turn_clockwise().turn_clockwise(), dir.opposite());
+        }
+    }
+
+    #[test]
+    fn direction_turns_follow_the_clockwise_order() {
+        let clockwise = Direction::ALL_DIRECTIONS_CLOCKWISE;
+        let counter_clockwise = Direction::ALL_DIRECTIONS_COUNTER_CLOCKWISE;
+        for index in 0..4 {
+            assert_eq!(clockwise[index].turn_clockwise(), &clockwise[(index + 1) % 4]);
+            assert_eq!(counter_clockwise[index].turn_counterclockwise(), &counter_clockwise[(index + 1) % 4]);
+            assert_eq!(clockwise[index].turn_clockwise().turn_counterclockwise(), &clockwise[index]);
+        }
+    }
+
+    #[test]
+    fn direction_any_orders_are_reversed() {
+        let clockwise = DirectionAny::ALL_DIRECTIONS_CLOCKWISE;
+        let counter_clockwise = DirectionAny::ALL_DIRECTIONS_COUNTER_CLOCKWISE;
+        for index in 0..8 {
+            assert_eq!(counter_clockwise[index], clockwise[(8 - index) % 8]);
+        }
+    }
+
+    #[test]
+    fn direction_any_diagonals_combine_simple_directions() {
+        let center = pos(1, 1);
+        for diagonal in [DirectionAny::UP_LEFT, DirectionAny::UP_RIGHT, DirectionAny::DOWN_LEFT, DirectionAny::DOWN_RIGHT] {
+            let DirectionAny::Diagonal([first, second]) = diagonal else {
+                panic!("{:?} isn't a diagonal", diagonal);
+            };
+            let expected = center.move_pos(&first, 3, 3).and_then(|moved| moved.move_pos(&second, 3, 3));
+            assert_eq!(center.move_pos_anydir(&diagonal, 3, 3), expected);
+            assert_ne!(expected, None);
+        }
+    }
+
+    #[test]
+    fn vec2d_turn_type() {
+        let right = Vec2D { x: 1, y: 0 };
+        assert_eq!(right.calc_turn_type(&Vec2D { x: 3, y: 0 }), TurnType::Strait);
+        assert_eq!(right.calc_turn_type(&Vec2D { x: -2, y: 0 }), TurnType::Opposite);
+        // y going down, turning from right to down is turning clockwise
+        assert_eq!(right.calc_turn_type(&Vec2D { x: 0, y: 1 }), TurnType::ClockWise(-1));
+        assert_eq!(right.calc_turn_type(&Vec2D { x: 0, y: -2 }), TurnType::CounterClockWise(2));
+        assert_eq!(Vec2D::new(&pos(2, 2), &pos(0, 3)), Vec2D { x: -2, y: 1 });
+    }
+
+    #[test]
+    fn map_valid_positions_of_a_non_square_map() {
+        let map = map_3x2();
+        assert_eq!((map.width(), map.height()), (3, 2));
+        assert!(map.is_valid_pos(&pos(2, 1)));
+        assert!(!map.is_valid_pos(&pos(3, 0)));
+        assert!(!map.is_valid_pos(&pos(0, 2)));
+        assert!(!map.is_valid_pos(&pos(2, 2)));
+        assert_eq!(map.get_opt(&pos(2, 1)), Some(&(2, 1)));
+        assert_eq!(map.get_opt(&pos(1, 2)), None);
+    }
+
+    #[test]
+    fn map_borders() {
+        let map = map_3x2();
+        assert!(map.is_left_border(&pos(0, 1)) && map.is_up_border(&pos(1, 0)));
+        assert!(map.is_right_border(&pos(2, 0)) && map.is_down_border(&pos(0, 1)));
+        assert!(!map.is_right_border(&pos(1, 0)));
+        let map = Map2D::new(vec![vec![0; 3]; 3]);
+        assert!(!map.is_border(&pos(1, 1)));
+        assert!(map.is_border(&pos(2, 1)));
+        assert_eq!(map.move_to_border(&pos(1, 1), &Direction::DOWN), pos(1, 2));
+        assert_eq!(map.move_to_border(&pos(1, 1), &Direction::RIGHT), pos(2, 1));
+    }
+
+    #[test]
+    fn map_set_and_get() {
+        let mut map = map_3x2();
+        map.set(&pos(2, 1), (9, 9));
+        *map.get_mut(&pos(0, 1)) = (8, 8);
+        assert_eq!(map.get(&pos(2, 1)), &(9, 9));
+        assert_eq!(map.get_content()[1], vec![(8, 8), (1, 1), (9, 9)]);
+        assert_eq!(map.get_mut_opt(&pos(0, 2)), None);
+    }
+
+    #[test]
+    fn iter_dir_stops_at_the_border() {
+        let map = map_3x2();
+        assert_eq!(map.iter_dir(pos(0, 0), Direction::RIGHT, true).collect::<Vec<_>>(), positions(&[(0, 0), (1, 0), (2, 0)]));
+        assert_eq!(map.iter_dir(pos(0, 0), Direction::RIGHT, false).collect::<Vec<_>>(), positions(&[(1, 0), (2, 0)]));
+        assert_eq!(map.iter_dir(pos(2, 0), Direction::DOWN, false).collect::<Vec<_>>(), positions(&[(2, 1)]));
+        assert_eq!(map.iter_dir(pos(2, 1), Direction::LEFT, false).collect::<Vec<_>>(), positions(&[(1, 1), (0, 1)]));
+        assert_eq!(map.iter_dir(pos(1, 0), Direction::UP, false).count(), 0);
+    }
+
+    #[test]
+    fn iter_all_orderings_of_a_non_square_map() {
+        let map = map_3x2();
+        let cases = [
+            ([Direction::RIGHT, Direction::DOWN], [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]),
+            ([Direction::RIGHT, Direction::UP], [(0, 1), (1, 1), (2, 1), (0, 0), (1, 0), (2, 0)]),
+            ([Direction::LEFT, Direction::DOWN], [(2, 0), (1, 0), (0, 0), (2, 1), (1, 1), (0, 1)]),
+            ([Direction::LEFT, Direction::UP], [(2, 1), (1, 1), (0, 1), (2, 0), (1, 0), (0, 0)]),
+            ([Direction::DOWN, Direction::RIGHT], [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]),
+            ([Direction::DOWN, Direction::LEFT], [(2, 0), (2, 1), (1, 0), (1, 1), (0, 0), (0, 1)]),
+            ([Direction::UP, Direction::RIGHT], [(0, 1), (0, 0), (1, 1), (1, 0), (2, 1), (2, 0)]),
+            ([Direction::UP, Direction::LEFT], [(2, 1), (2, 0), (1, 1), (1, 0), (0, 1), (0, 0)]),
+        ];
+        for (dirs, expected) in cases {
+            assert_eq!(map.iter_all(&[&dirs[0], &dirs[1]]).collect::<Vec<_>>(), positions(&expected), "{:?}", dirs);
+        }
+        assert_eq!(map.iter_all_fast().collect::<Vec<_>>(), positions(&cases[0].1));
+    }
+
+    #[test]
+    fn iter_all_visits_every_cell_of_a_tall_map_once() {
+        let map = Map2D::new(vec![vec![0; 2]; 5]);
+        for first in Direction::ALL_DIRECTIONS_CLOCKWISE {
+            for second in [first.turn_clockwise(), first.turn_counterclockwise()] {
+                let mut visited: Vec<Pos> = map.iter_all(&[&first, second]).collect();
+                assert_eq!(visited.len(), 10, "{:?} then {:?}", first, second);
+                assert!(visited.iter().all(|pos| map.is_valid_pos(pos)));
+                visited.sort_by_key(|pos| (pos.y, pos.x));
+                visited.dedup();
+                assert_eq!(visited.len(), 10, "{:?} then {:?}", first, second);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot manage")]
+    fn iter_all_needs_perpendicular_directions() {
+        map_3x2().iter_all(&[&Direction::LEFT, &Direction::RIGHT]);
+    }
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn from_lines_maps_each_character_at_its_position() {
+        let map = Map2D::from_lines(&lines(&["ab", "cd", "ef"]), |c, pos| (c, pos)).unwrap();
+        assert_eq!((map.width(), map.height()), (2, 3));
+        assert_eq!(*map.get(&pos(1, 0)), ('b', pos(1, 0)));
+        assert_eq!(*map.get(&pos(0, 2)), ('e', pos(0, 2)));
+    }
+
+    #[test]
+    fn from_lines_needs_rows_of_the_same_width() {
+        assert_eq!(Map2D::from_lines(&lines(&[]), |c, _| c).err(), Some(ParseError::empty_input()));
+        let error = Map2D::from_lines(&lines(&["", ""]), |c, _| c).err().unwrap();
+        assert_eq!((error.line, error.message.as_str()), (1, "empty row"));
+        let error = Map2D::from_lines(&lines(&["ab", "abc", "ab"]), |c, _| c).err().unwrap();
+        assert_eq!((error.line, error.message.as_str()), (2, "width isn't 2 in"));
+        // the width is counted in characters, not in bytes
+        assert!(Map2D::from_lines(&lines(&["éa", "ab"]), |c, _| c).is_ok());
+        assert!(Map2D::from_lines(&lines(&["é", "ab"]), |c, _| c).is_err());
+    }
+
+    #[test]
+    fn every_constructor_reports_the_errors_of_grid_width() {
+        for grid in [vec![], vec!["", ""], vec!["ab", "abc", "ab"]] {
+            let grid = lines(&grid);
+            let expected = grid_width(&grid).err();
+            assert_eq!(Map2D::from_lines(&grid, |c, _| c).err(), expected);
+            assert_eq!(Map2D::try_from_lines(&grid, |c, _| Ok(c)).err(), expected);
+            assert_eq!(Map2D::try_from_lines_marked(&grid, |c, _| Ok(c), |_| false).err(), expected);
+        }
+    }
+
+    #[test]
+    fn try_from_lines_reports_the_position_of_invalid_characters() {
+        let error = Map2D::try_from_lines(&lines(&["12", "3x"]), |c, _| c.to_digit(10).ok_or("invalid digit")).err().unwrap();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+        assert_eq!(error.message, "invalid digit");
+    }
+
+    #[test]
+    fn try_from_lines_marked_gives_the_marked_positions_row_by_row() {
+        let (map, marked) = Map2D::try_from_lines_marked(&lines(&[".#.", "#.#"]), |c, _| Ok(c == '#'), |wall| *wall).unwrap();
+        assert_eq!((map.width(), map.height()), (3, 2));
+        assert_eq!(marked, positions(&[(1, 0), (0, 1), (2, 1)]));
+    }
+
+    #[test]
+    fn new_map_can_be_empty() {
+        let map: Map2D<u8> = Map2D::new(vec![]);
+        assert_eq!((map.width(), map.height()), (0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "same width")]
+    fn new_map_needs_rows_of_the_same_width() {
+        Map2D::new(vec![vec![1, 2], vec![3]]);
+    }
+}
@@ -0,0 +1,36 @@
+use std::cell::RefCell;
+use std::fmt::{Arguments, Write};
+
+thread_local! {
+    static BUFFER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Prints a line, or adds it to the output captured by the current thread
+#[macro_export]
+macro_rules! outln {
+    () => (
+        $crate::output::write_line(format_args!(""))
+    );
+    ($($arg:tt)*) => (
+        $crate::output::write_line(format_args!($($arg)*))
+    );
+}
+
+pub fn write(args: Arguments) {
+    BUFFER.with(|buffer| match buffer.borrow_mut().as_mut() {
+        Some(buffer) => buffer.write_fmt(args).unwrap(),
+        None => print!("{}", args),
+    });
+}
+
+pub fn write_line(args: Arguments) {
+    write(format_args!("{}\n", args));
+}
+
+/// Runs `fct`, returning the output it wrote instead of printing it, so that jobs run in parallel don't mix their lines
+pub fn capture<T>(fct: impl FnOnce() -> T) -> (T, String) {
+    let previous = BUFFER.with(|buffer| buffer.replace(Some(String::new())));
+    let result = fct();
+    let output = BUFFER.with(|buffer| buffer.replace(previous)).unwrap_or_default();
+    (result, output)
+}
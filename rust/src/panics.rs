@@ -0,0 +1,74 @@
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+/// A panic caught while running a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub message: String,
+    /// `file:line:column` where the panic occurred, when known
+    pub location: Option<String>,
+    /// Backtrace of the panic, only captured when enabled with `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+    pub backtrace: Option<String>,
+}
+
+impl Display for PanicReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "'{}' at {}", self.message, location),
+            None => write!(f, "'{}'", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+    static LAST_BACKTRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Keeps the default panic output for panics that aren't caught by `catch`, and records the location and backtrace of the others
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(|catching| catching.get()) {
+                let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+                LAST_LOCATION.with(|last| *last.borrow_mut() = location);
+                let backtrace = Backtrace::capture();
+                let backtrace = (backtrace.status() == BacktraceStatus::Captured).then(|| backtrace.to_string());
+                LAST_BACKTRACE.with(|last| *last.borrow_mut() = backtrace);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs `fct`, turning a panic into a report instead of unwinding further
+pub fn catch<T>(fct: impl FnOnce() -> T) -> Result<T, PanicReport> {
+    install_hook();
+    let was_catching = CATCHING.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(fct));
+    CATCHING.with(|catching| catching.set(was_catching));
+    result.map_err(|payload| PanicReport {
+        message: payload_message(payload.as_ref()),
+        location: LAST_LOCATION.with(|last| last.borrow_mut().take()),
+        backtrace: LAST_BACKTRACE.with(|last| last.borrow_mut().take()),
+    })
+}
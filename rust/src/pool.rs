@@ -0,0 +1,27 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of threads used when the parallelism isn't given
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map(|nb| nb.get()).unwrap_or(1)
+}
+
+/// Applies `fct` to every item on a pool of `threads` threads, returning the results in the order of the items
+pub fn map<T: Sync, R: Send>(items: &[T], threads: usize, fct: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = fct(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+    results.into_inner().unwrap().into_iter().map(|result| result.unwrap()).collect()
+}
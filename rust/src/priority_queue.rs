@@ -46,6 +46,12 @@ pub struct PriorityQueue<C: Ord + Copy + Clone/*Cost */, K: Hash + Copy + Clone/
     queue: BinaryHeap<Wrapper<C,K, T>>,
 }
 
+impl<C: Ord + Copy + Clone, K: Hash + Eq + Copy + Clone, T: Cost<C> + Key<K>> Default for PriorityQueue<C, K, T> {
+    fn default() -> Self {
+        PriorityQueue::new()
+    }
+}
+
 #[allow(dead_code)]
 impl<C: Ord + Copy + Clone, K: Hash + Eq + Copy + Clone, T: Cost<C> + Key<K>>
     PriorityQueue<C, K, T>
@@ -88,3 +94,200 @@ impl<C: Ord + Copy + Clone, K: Hash + Eq + Copy + Clone, T: Cost<C> + Key<K>>
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// SplitMix64, enough randomness for the properties while keeping each case reproducible from its seed
+    struct Random(u64);
+
+    impl Random {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        fn below(&mut self, max: u64) -> u64 {
+            self.next() % max
+        }
+    }
+
+    const SEEDS: u64 = 500;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Item {
+        key: u8,
+        cost: u32,
+        /// Tells apart the pushes of a same key and cost
+        id: usize,
+    }
+
+    impl Cost<u32> for Item {
+        fn cost(&self) -> u32 {
+            self.cost
+        }
+    }
+
+    impl Key<u8> for Item {
+        fn key(&self) -> u8 {
+            self.key
+        }
+    }
+
+    /// Naive queue with the same contract: a push is kept only if its cost is below every previous push of its key,
+    /// so at most one item per key can be popped, the one with the cheapest cost
+    #[derive(Default)]
+    struct Reference {
+        best: HashMap<u8, u32>,
+        live: HashMap<u8, Item>,
+    }
+
+    impl Reference {
+        fn push(&mut self, item: Item) {
+            if self.best.get(&item.key).is_some_and(|best| *best <= item.cost) {
+                return;
+            }
+            self.best.insert(item.key, item.cost);
+            self.live.insert(item.key, item);
+        }
+
+        /// Checks that the popped item is one of the cheapest live ones, any of them being valid on ties
+        fn pop(&mut self, popped: Option<Item>, seed: u64) {
+            let min_cost = self.live.values().map(|item| item.cost).min();
+            assert_eq!(popped.map(|item| item.cost), min_cost, "seed {}", seed);
+            if let Some(item) = popped {
+                assert_eq!(self.live.remove(&item.key), Some(item), "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn push_keeps_only_cheaper_costs_of_a_key() {
+        let mut queue: PriorityQueue<u32, u8, Item> = PriorityQueue::new();
+        queue.push(Item { key: 1, cost: 5, id: 0 });
+        queue.push(Item { key: 1, cost: 5, id: 1 });
+        queue.push(Item { key: 1, cost: 7, id: 2 });
+        queue.push(Item { key: 1, cost: 3, id: 3 });
+        queue.push(Item { key: 2, cost: 4, id: 4 });
+        assert_eq!(queue.pop().map(|item| item.id), Some(3));
+        assert_eq!(queue.pop().map(|item| item.id), Some(4));
+        // the costlier push of key 1 is stale and skipped
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn key_can_be_pushed_again_with_a_lower_cost_after_pop() {
+        let mut queue: PriorityQueue<u32, u8, Item> = PriorityQueue::default();
+        queue.push(Item { key: 1, cost: 5, id: 0 });
+        assert_eq!(queue.pop().map(|item| item.id), Some(0));
+        queue.push(Item { key: 1, cost: 5, id: 1 });
+        assert_eq!(queue.pop(), None);
+        queue.push(Item { key: 1, cost: 2, id: 2 });
+        assert_eq!(queue.pop().map(|item| item.id), Some(2));
+    }
+
+    #[test]
+    fn ties_pop_every_key() {
+        let mut queue: PriorityQueue<u32, u8, Item> = PriorityQueue::new();
+        (0..10).for_each(|key| queue.push(Item { key, cost: 1, id: key as usize }));
+        let mut popped: Vec<u8> = std::iter::from_fn(|| queue.pop()).map(|item| item.key).collect();
+        popped.sort();
+        assert_eq!(popped, (0..10).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn random_push_pop_sequences_match_the_reference() {
+        for seed in 0..SEEDS {
+            let mut random = Random(seed);
+            let mut queue: PriorityQueue<u32, u8, Item> = PriorityQueue::new();
+            let mut reference = Reference::default();
+            // few keys and costs so that ties, stale pushes and re-insertions are frequent
+            let nb_keys = 1 + random.below(8);
+            let max_cost = 1 + random.below(10);
+            for id in 0..(random.below(200) as usize) {
+                if random.below(3) == 0 {
+                    reference.pop(queue.pop(), seed);
+                } else {
+                    let item = Item {
+                        key: random.below(nb_keys) as u8,
+                        cost: random.below(max_cost) as u32,
+                        id,
+                    };
+                    queue.push(item);
+                    reference.push(item);
+                }
+            }
+            while !reference.live.is_empty() {
+                reference.pop(queue.pop(), seed);
+            }
+            assert_eq!(queue.pop(), None, "seed {}", seed);
+        }
+    }
+
+    /// Random directed graph as adjacency lists of (target, weight), with zero weights to get ties
+    fn random_graph(random: &mut Random) -> Vec<Vec<(usize, u32)>> {
+        let nb_nodes = 1 + random.below(30) as usize;
+        let nb_edges = random.below(4 * nb_nodes as u64) as usize;
+        let mut graph = vec![vec![]; nb_nodes];
+        for _ in 0..nb_edges {
+            let from = random.below(nb_nodes as u64) as usize;
+            let to = random.below(nb_nodes as u64) as usize;
+            graph[from].push((to, random.below(10) as u32));
+        }
+        graph
+    }
+
+    fn dijkstra(graph: &[Vec<(usize, u32)>]) -> Vec<Option<u32>> {
+        let mut distances = vec![None; graph.len()];
+        let mut queue: PriorityQueue<u32, u8, Item> = PriorityQueue::new();
+        queue.push(Item { key: 0, cost: 0, id: 0 });
+        while let Some(item) = queue.pop() {
+            let node = item.key as usize;
+            if distances[node].is_some() {
+                continue;
+            }
+            distances[node] = Some(item.cost);
+            for (to, weight) in &graph[node] {
+                queue.push(Item {
+                    key: *to as u8,
+                    cost: item.cost + weight,
+                    id: 0,
+                });
+            }
+        }
+        distances
+    }
+
+    /// Bellman-Ford, too slow for the puzzles but obviously right
+    fn reference_distances(graph: &[Vec<(usize, u32)>]) -> Vec<Option<u32>> {
+        let mut distances = vec![None; graph.len()];
+        distances[0] = Some(0);
+        for _ in 0..graph.len() {
+            for (from, edges) in graph.iter().enumerate() {
+                let Some(from_distance) = distances[from] else {
+                    continue;
+                };
+                for (to, weight) in edges {
+                    if distances[*to].is_none_or(|distance| from_distance + weight < distance) {
+                        distances[*to] = Some(from_distance + weight);
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn dijkstra_on_random_graphs_matches_the_reference() {
+        for seed in 0..SEEDS {
+            let graph = random_graph(&mut Random(seed));
+            assert_eq!(dijkstra(&graph), reference_distances(&graph), "seed {}", seed);
+        }
+    }
+}
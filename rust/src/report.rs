@@ -0,0 +1,300 @@
+use std::fs;
+use std::io;
+use std::sync::Mutex;
+
+use crate::answers::Value;
+use crate::bench::Stats;
+use crate::utils::{Dataset, Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Wrong,
+    /// No expected value to compare with
+    Unchecked,
+    /// The puzzle didn't return an answer for the part
+    Missing,
+    /// The input couldn't be read or parsed
+    Failed,
+    Panicked,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Wrong => "wrong",
+            Status::Unchecked => "unchecked",
+            Status::Missing => "missing",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Status::Ok | Status::Unchecked)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: Part,
+    pub value: Option<Value>,
+    pub expected: Option<Value>,
+    pub status: Status,
+}
+
+/// Outcome of one run of a day, for a part (or both parts in a global run) and a dataset
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub day: u8,
+    pub data_set: Dataset,
+    pub parts: Vec<PartReport>,
+    /// Error or panic message of a failed run
+    pub error: Option<String>,
+    /// Time spent reading the input file
+    pub read_ms: f32,
+    /// Time of the puzzle call, the median one in bench mode
+    pub solve_ms: f32,
+    pub bench: Option<Stats>,
+    /// Phases timed by the puzzle (median durations in bench mode)
+    pub phases: Vec<(&'static str, f32)>,
+}
+
+impl RunReport {
+    pub fn phase(&self, name: &str) -> Option<f32> {
+        self.phases.iter().find(|(phase, _)| *phase == name).map(|(_, duration)| *duration)
+    }
+}
+
+static REPORTS: Mutex<Vec<RunReport>> = Mutex::new(Vec::new());
+
+pub fn add(report: RunReport) {
+    REPORTS.lock().unwrap().push(report);
+}
+
+/// Removes the reports of the runs so far, in execution order
+pub fn take() -> Vec<RunReport> {
+    std::mem::take(&mut *REPORTS.lock().unwrap())
+}
+
+/// Reports of every run so far, ordered by day then execution order
+pub fn all() -> Vec<RunReport> {
+    let mut reports = REPORTS.lock().unwrap().clone();
+    reports.sort_by_key(|report| report.day);
+    reports
+}
+
+pub fn json_string(str: &str) -> String {
+    let mut result = String::with_capacity(str.len() + 2);
+    result.push('"');
+    for c in str.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_value(value: &Option<Value>) -> String {
+    match value {
+        Some(Value::Int(v)) => v.to_string(),
+        Some(Value::Text(v)) => json_string(v),
+        None => "null".to_string(),
+    }
+}
+
+fn json_phases(phases: &[(&'static str, f32)]) -> String {
+    let phases: Vec<String> = phases
+        .iter()
+        .map(|(name, duration)| format!("{}:{:.3}", json_string(name), duration))
+        .collect();
+    format!("{{{}}}", phases.join(","))
+}
+
+fn json_stats(stats: &Stats) -> String {
+    format!(
+        "{{\"iterations\":{},\"outliers\":{},\"min_ms\":{:.4},\"median_ms\":{:.4},\"p95_ms\":{:.4},\"max_ms\":{:.4},\"mean_ms\":{:.4},\"stddev_ms\":{:.4}}}",
+        stats.iterations, stats.outliers, stats.min, stats.median, stats.p95, stats.max, stats.mean, stats.stddev
+    )
+}
+
+/// ` (parse 0.12 ms, part1 1.30 ms)`, or nothing when the puzzle doesn't time its phases
+pub fn format_phases(phases: &[(&'static str, f32)]) -> String {
+    if phases.is_empty() {
+        return String::new();
+    }
+    let phases: Vec<String> = phases.iter().map(|(name, duration)| format!("{} {:.2} ms", name, duration)).collect();
+    format!(" ({})", phases.join(", "))
+}
+
+/// One line per benchmarked day, part and dataset
+pub fn bench_summary(reports: &[RunReport]) -> Vec<String> {
+    reports
+        .iter()
+        .filter_map(|report| {
+            let stats = report.bench.as_ref()?;
+            let parts = match report.parts.as_slice() {
+                [part] => format!("{:?}", part.part),
+                _ => "ALL".to_string(),
+            };
+            Some(format!(
+                "Day {:>2} {:<5} {:<8} {}{}",
+                report.day,
+                parts,
+                report.data_set,
+                stats,
+                format_phases(&report.phases)
+            ))
+        })
+        .collect()
+}
+
+/// One JSON object per day, part and dataset, with the failures of the whole run (those deciding the exit code)
+pub fn to_json(reports: &[RunReport], duration_ms: u64, failures: usize) -> String {
+    let results: Vec<String> = reports
+        .iter()
+        .flat_map(|report| {
+            report.parts.iter().map(move |part| {
+                format!(
+                    "{{\"day\":{},\"part\":{},\"dataset\":{},\"answer\":{},\"expected\":{},\"status\":{},\"error\":{},\"read_ms\":{:.3},\"parse_ms\":{},\"solve_ms\":{:.3},\"phases\":{},\"bench\":{}}}",
+                    report.day,
+                    match part.part {
+                        Part::Part1 => 1,
+                        Part::Part2 => 2,
+                    },
+                    json_string(&report.data_set.name()),
+                    json_value(&part.value),
+                    json_value(&part.expected),
+                    json_string(part.status.name()),
+                    report.error.as_deref().map(json_string).unwrap_or("null".to_string()),
+                    report.read_ms,
+                    report.phase("parse").map(|duration| format!("{:.3}", duration)).unwrap_or("null".to_string()),
+                    report.solve_ms,
+                    json_phases(&report.phases),
+                    report.bench.as_ref().map(json_stats).unwrap_or("null".to_string())
+                )
+            })
+        })
+        .collect();
+    format!(
+        "{{\"duration_ms\":{},\"failures\":{},\"results\":[{}]}}",
+        duration_ms,
+        failures,
+        results.join(",")
+    )
+}
+
+fn xml_escape(str: &str) -> String {
+    str.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn optional_value(value: &Option<Value>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or("none".to_string())
+}
+
+/// Time of the testcase of a part, in ms. The parts of a simultaneous run share its time: each part takes the time of
+/// its phase, the first one also taking the rest of the run (reading, parsing), or the whole run when the puzzle
+/// doesn't time its parts, so that the time of a run is counted once.
+fn part_time(report: &RunReport, part: Part) -> f32 {
+    let total = report.read_ms + report.solve_ms;
+    let phase = |part: Part| match part {
+        Part::Part1 => report.phase("part1"),
+        Part::Part2 => report.phase("part2"),
+    };
+    if report.parts.len() < 2 {
+        return total;
+    }
+    let others = report.parts.iter().filter(|other| other.part != part).map(|other| phase(other.part));
+    match (report.parts[0].part == part, phase(part)) {
+        (false, duration) => duration.unwrap_or(0.0),
+        (true, _) => others.fold(total, |rest, duration| rest - duration.unwrap_or(0.0)).max(0.0),
+    }
+}
+
+/// JUnit XML report, each day being a testsuite and each part and dataset a testcase
+pub fn to_junit(reports: &[RunReport], duration_ms: u64) -> String {
+    let mut days: Vec<u8> = reports.iter().map(|report| report.day).collect();
+    days.dedup();
+    let count = |reports: &mut dyn Iterator<Item = &RunReport>, statuses: &[Status]| {
+        reports
+            .flat_map(|report| report.parts.iter())
+            .filter(|part| statuses.contains(&part.status))
+            .count()
+    };
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"advent_of_code_2023\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+        reports.iter().map(|report| report.parts.len()).sum::<usize>(),
+        count(&mut reports.iter(), &[Status::Wrong, Status::Missing]),
+        count(&mut reports.iter(), &[Status::Failed, Status::Panicked]),
+        duration_ms as f32 / 1000.0
+    ));
+    for day in days {
+        let day_reports: Vec<&RunReport> = reports.iter().filter(|report| report.day == day).collect();
+        xml.push_str(&format!(
+            "  <testsuite name=\"day{:02}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+            day,
+            day_reports.iter().map(|report| report.parts.len()).sum::<usize>(),
+            count(&mut day_reports.iter().copied(), &[Status::Wrong, Status::Missing]),
+            count(&mut day_reports.iter().copied(), &[Status::Failed, Status::Panicked]),
+            count(&mut day_reports.iter().copied(), &[Status::Unchecked]),
+            day_reports.iter().map(|report| report.read_ms + report.solve_ms).sum::<f32>() / 1000.0
+        ));
+        for report in day_reports {
+            for part in &report.parts {
+                xml.push_str(&format!(
+                    "    <testcase classname=\"day{:02}\" name=\"{:?}/{}\" time=\"{:.6}\"",
+                    day,
+                    part.part,
+                    xml_escape(&report.data_set.to_string()),
+                    part_time(report, part.part) / 1000.0
+                ));
+                let error = xml_escape(report.error.as_deref().unwrap_or_default());
+                let content = match part.status {
+                    Status::Ok => None,
+                    Status::Wrong => Some(format!(
+                        "<failure message=\"{} instead of {}\" type=\"WrongAnswer\"/>",
+                        xml_escape(&optional_value(&part.value)),
+                        xml_escape(&optional_value(&part.expected))
+                    )),
+                    Status::Missing => Some("<failure message=\"No result returned\" type=\"MissingAnswer\"/>".to_string()),
+                    Status::Unchecked => Some(format!(
+                        "<skipped message=\"Result {} not checked (no expected value)\"/>",
+                        xml_escape(&optional_value(&part.value))
+                    )),
+                    Status::Failed => Some(format!("<error message=\"{}\" type=\"InputError\"/>", error)),
+                    Status::Panicked => Some(format!("<error message=\"{}\" type=\"Panic\"/>", error)),
+                };
+                match content {
+                    Some(content) => xml.push_str(&format!(">\n      {}\n    </testcase>\n", content)),
+                    None => xml.push_str("/>\n"),
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Writes the content to the given file, `-` being the standard output
+pub fn write(target: &str, content: &str) -> io::Result<()> {
+    if target == "-" {
+        println!("{}", content);
+        Ok(())
+    } else {
+        fs::write(target, content)
+    }
+}
@@ -0,0 +1,238 @@
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2023::days::{self, SOURCE_DIR};
+use advent_of_code_2023::inputs::DataDir;
+
+const TEMPLATE: &str = "day.temp.rs";
+
+const TESTS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/days.rs");
+
+const FUZZ_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz");
+
+/// Width of the source lines, as set in rustfmt.toml
+const MAX_WIDTH: usize = 150;
+
+fn source_path(file: &str) -> PathBuf {
+    Path::new(SOURCE_DIR).join(file)
+}
+
+/// Writes a file that must not exist yet
+fn create(path: &Path, content: &str) -> Result<(), String> {
+    File::create_new(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| format!("Cannot create {}: {}", path.display(), e))
+}
+
+/// A file written by `new_day`, with its content before, `None` when it is created
+struct Change {
+    path: PathBuf,
+    content: String,
+    previous: Option<String>,
+}
+
+impl Change {
+    fn update(path: PathBuf, content: String, previous: String) -> Change {
+        Change { path, content, previous: Some(previous) }
+    }
+
+    fn create(path: PathBuf, content: String) -> Change {
+        Change { path, content, previous: None }
+    }
+
+    fn apply(&self) -> Result<(), String> {
+        match self.previous {
+            Some(_) => write(&self.path, &self.content),
+            None => create(&self.path, &self.content),
+        }
+    }
+
+    fn revert(&self) -> std::io::Result<()> {
+        match &self.previous {
+            Some(previous) => fs::write(&self.path, previous),
+            None => fs::remove_file(&self.path),
+        }
+    }
+}
+
+/// Writes all the files, restoring the ones already written when one of them fails
+fn apply_all(changes: &[Change]) -> Result<(), String> {
+    for (index, change) in changes.iter().enumerate() {
+        if let Err(e) = change.apply() {
+            for done in changes[..index].iter().rev() {
+                if let Err(revert_error) = done.revert() {
+                    return Err(format!("{}, and cannot restore {}: {}", e, done.path.display(), revert_error));
+                }
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Adds `pub mod dayNN;` after the last day module
+fn register_module(lib: &str, module: &str) -> Result<String, String> {
+    let last = lib
+        .lines()
+        .rfind(|line| line.starts_with("pub mod day") && line.ends_with(';'))
+        .ok_or("no day module found in lib.rs")?;
+    let end = lib.find(last).unwrap() + last.len();
+    Ok(format!("{}\npub mod {};{}", &lib[..end], module, &lib[end..]))
+}
+
+/// `use crate::{day01, day02};`, wrapped the way rustfmt does when too long
+fn use_days(modules: &[String]) -> String {
+    let line = format!("use crate::{{{}}};", modules.join(", "));
+    if line.len() <= MAX_WIDTH {
+        return line;
+    }
+    let mut lines = vec![String::from("use crate::{")];
+    let mut current = String::new();
+    for module in modules {
+        if !current.is_empty() && 4 + current.len() + module.len() + 2 > MAX_WIDTH {
+            lines.push(format!("    {}", current.trim_end()));
+            current.clear();
+        }
+        current.push_str(&format!("{}, ", module));
+    }
+    lines.push(format!("    {}", current.trim_end()));
+    lines.push(String::from("};"));
+    lines.join("\n")
+}
+
+/// Adds the module to the imports of the registry, and its definition at the end of `DAYS`
+fn register_day(registry: &str, day: u8, module: &str, title: &str) -> Result<String, String> {
+    let start = registry
+        .find("use crate::{day")
+        .or_else(|| registry.find("use crate::{\n    day"))
+        .ok_or("no import of the days found in days.rs")?;
+    let end = start + registry[start..].find("};").ok_or("unterminated import of the days in days.rs")? + 2;
+    let mut modules: Vec<String> = registry[start..end]
+        .trim_start_matches("use crate::{")
+        .trim_end_matches("};")
+        .split(',')
+        .map(|module| module.trim().to_string())
+        .filter(|module| !module.is_empty())
+        .collect();
+    modules.push(module.to_string());
+    let registry = format!("{}{}{}", &registry[..start], use_days(&modules), &registry[end..]);
+
+    let days_start = registry
+        .find("pub const DAYS: &[DayDef] = &[")
+        .ok_or("no DAYS registry found in days.rs")?;
+    let days_end = days_start + registry[days_start..].find("\n];").ok_or("unterminated DAYS registry in days.rs")?;
+    Ok(format!(
+        "{}\n    DayDef::per_part({}, {:?}, {}::puzzle),{}",
+        &registry[..days_end],
+        day,
+        title,
+        module,
+        &registry[days_end..]
+    ))
+}
+
+/// Adds `dayNN: N,` at the end of the `day_tests!` list of the integration tests, ignored until the day has answers
+fn register_tests(tests: &str, day: u8, module: &str) -> Result<String, String> {
+    let start = tests.find("day_tests! {").ok_or("no day_tests! list found in tests/days.rs")?;
+    let end = start + tests[start..].find("\n}").ok_or("unterminated day_tests! list in tests/days.rs")?;
+    Ok(format!(
+        "{}\n    #[ignore = \"no answers in data/answers.toml yet\"]\n    {}: {},{}",
+        &tests[..end],
+        module,
+        day,
+        &tests[end..]
+    ))
+}
+
+/// Adds the `[[bin]]` of the fuzz target at the end of the manifest of the fuzz crate
+fn register_fuzz_target(manifest: &str, module: &str) -> Result<String, String> {
+    if manifest.lines().any(|line| line.trim() == format!("name = \"{}\"", module)) {
+        return Err(format!("the fuzz target {} is already listed in fuzz/Cargo.toml", module));
+    }
+    Ok(format!(
+        "{}\n[[bin]]\nname = \"{1}\"\npath = \"fuzz_targets/{1}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        manifest, module
+    ))
+}
+
+fn fuzz_target(day: u8) -> String {
+    format!(
+        "#![no_main]\n\nlibfuzzer_sys::fuzz_target!(|data: &[u8]| advent_of_code_2023_fuzz::fuzz_day({}, data));\n",
+        day
+    )
+}
+
+/// Creates the file unless it already exists, returning whether it was created
+fn create_empty(path: &Path) -> Result<bool, String> {
+    match File::create_new(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(format!("Cannot create {}: {}", path.display(), e)),
+    }
+}
+
+/// Generates the source of a new day from the template, registers it, and creates its empty input files.
+/// Nothing is written when the day already exists, or when it would leave days missing in the registry.
+/// Returns what was done, one line per file.
+pub fn new_day(day: u8, title: Option<&str>, data_dir: &DataDir) -> Result<Vec<String>, String> {
+    let module = format!("day{:02}", day);
+    let source = source_path(&format!("{}.rs", module));
+    if source.exists() {
+        return Err(format!("{} already exists", source.display()));
+    }
+    if let Some(def) = days::get(day) {
+        return Err(format!("Day {} ({}) is already registered", day, def.title));
+    }
+    let next_day = days::DAYS.iter().map(|def| def.day).max().unwrap_or(0) + 1;
+    if day != next_day {
+        return Err(format!(
+            "Day {} is the next day to create, the registry can't have missing days",
+            next_day
+        ));
+    }
+    let title = title.map(str::to_string).unwrap_or_else(|| format!("Day {}", day));
+
+    let (lib_path, registry_path) = (source_path("lib.rs"), source_path("days.rs"));
+    let tests_path = Path::new(TESTS_FILE).to_path_buf();
+    let (fuzz_manifest_path, fuzz_target_path) = (
+        Path::new(FUZZ_DIR).join("Cargo.toml"),
+        Path::new(FUZZ_DIR).join("fuzz_targets").join(format!("{}.rs", module)),
+    );
+    if fuzz_target_path.exists() {
+        return Err(format!("{} already exists", fuzz_target_path.display()));
+    }
+    let (lib, registry, tests, fuzz_manifest) = (read(&lib_path)?, read(&registry_path)?, read(&tests_path)?, read(&fuzz_manifest_path)?);
+    // all the contents are built before writing anything, and the files written are restored on failure
+    let changes = [
+        Change::create(source.clone(), read(&source_path(TEMPLATE))?),
+        Change::update(lib_path.clone(), register_module(&lib, &module)?, lib),
+        Change::update(registry_path.clone(), register_day(&registry, day, &module, &title)?, registry),
+        Change::update(tests_path.clone(), register_tests(&tests, day, &module)?, tests),
+        Change::update(fuzz_manifest_path, register_fuzz_target(&fuzz_manifest, &module)?, fuzz_manifest),
+        Change::create(fuzz_target_path.clone(), fuzz_target(day)),
+    ];
+    apply_all(&changes)?;
+    let mut done = vec![
+        format!("created {}", source.display()),
+        format!("registered {} in {} and {}", module, lib_path.display(), registry_path.display()),
+        format!("added the tests of {} to {}, ignored until its answers are known", module, tests_path.display()),
+        format!("created the fuzz target {}", fuzz_target_path.display()),
+    ];
+    for name in [format!("day_{}.dat", day), format!("day_{}_test.dat", day)] {
+        let path = data_dir.path().join(name);
+        done.push(match create_empty(&path)? {
+            true => format!("created {}", path.display()),
+            false => format!("kept the existing {}", path.display()),
+        });
+    }
+    Ok(done)
+}
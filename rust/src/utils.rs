@@ -1,51 +1,48 @@
 use std::fmt::Display;
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, Error, Lines};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicUsize;
-use std::time::Instant;
+use std::sync::Mutex;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::answers::{Answer, AnswersStore, PartAnswer, Value};
+use crate::bench::{BenchConfig, Stats};
+use crate::error::{ParseError, PuzzleError};
+use crate::inputs::DataDir;
+use crate::logger::{Logger, Record};
+use crate::output;
+use crate::outln;
+use crate::panics;
+use crate::report::{self, PartReport, RunReport, Status};
 
 #[macro_export]
 macro_rules!
 log {
     ($level:tt,$ctxt:expr,$msg:expr) => (
-        $ctxt.$level(|| println!($msg))
+        $ctxt.$level(|| format!($msg))
     );
     ($level:tt, $ctxt:expr,$msg:expr, $($other:expr) ,*) => (
-        $ctxt.$level(|| println!($msg,$($other , )+))
+        $ctxt.$level(|| format!($msg,$($other , )+))
     );
 }
 
 #[macro_export]
 macro_rules! check_result {
-    ($ctxt:expr, [$res_p1:expr, $res_p2:expr ], [ $res_p1_test:expr,$res_p1_real:expr, $res_p2_test:expr, $res_p2_real:expr ] ) => {
-        if $ctxt.is_bench() {
-            return;
-        }
+    ($ctxt:expr, [$res_p1:expr, $res_p2:expr ]) => {{
         if $ctxt.has_part() {
             panic!("Shoudn't be call in separate run context")
         }
-        if ($ctxt.is_test()) {
-            $ctxt.check_both(($res_p1, $res_p2), ($res_p1_test, $res_p2_test))
-        } else {
-            $ctxt.check_both(($res_p1, $res_p2), ($res_p1_real, $res_p2_real))
-        }
-    };
-
-    ($ctxt:expr, $res:expr, [ $res1:expr, $res2:expr ] ) => {
-        if $ctxt.is_bench() {
-            return;
-        }
+        Ok($crate::answers::Answer::both(($res_p1, $res_p2)))
+    }};
 
-        if !$ctxt.has_part() {
-            panic!("Shoudn't be call in mono run context")
+    ($ctxt:expr, $res:expr) => {{
+        match $ctxt.part() {
+            Some(part) => Ok($crate::answers::Answer::part(part, $res)),
+            None => panic!("Shoudn't be call in mono run context"),
         }
-        if ($ctxt.is_test()) {
-            $ctxt.check($res, $res1)
-        } else {
-            $ctxt.check($res, $res2)
-        }
-    };
+    }};
 }
 
 fn read_lines_internal<P>(filename: P) -> Result<Lines<BufReader<File>>, Error>
@@ -56,53 +53,29 @@ where
     Ok(io::BufReader::new(file).lines())
 }
 
-fn get_applicable_filename_default(day: &u8, is_test: &Dataset) -> String {
-    return format!(
-        "../data/day_{}{}.dat",
-        day,
-        match is_test {
-            Dataset::Test => "_test",
-            _ => "",
-        }
-    );
-}
-
-fn get_applicable_filename(day: &u8, part: Option<Part>, is_test: &Dataset) -> String {
-    return part
-        .map(|p| {
-            format!(
-                "../data/day_{}_{}{}.dat",
-                day,
-                match p {
-                    Part::Part1 => 1,
-                    Part::Part2 => 2,
-                },
-                match is_test {
-                    Dataset::Test => "_test",
-                    _ => "",
-                }
-            )
-        })
-        .filter(|name| std::path::Path::new(name.as_str()).exists())
-        .unwrap_or_else(|| get_applicable_filename_default(day, is_test));
-}
-
-pub fn read_lines(day: &u8, part: Option<Part>, is_test: &Dataset) -> Option<Lines<BufReader<File>>> {
-    let f = read_lines_internal(get_applicable_filename(day, part, is_test));
-
-    return match f {
-        Ok(lines) => Some(lines),
-        Err(_) => {
-            println!("No file found");
-            None
-        }
+pub fn read_lines(context: &Context) -> Result<(PathBuf, Lines<BufReader<File>>), PuzzleError> {
+    let path = context
+        .data_dir
+        .find_input(context.day, context.part, &context.data_set)
+        .ok_or_else(|| PuzzleError::MissingInput {
+            day: context.day,
+            data_set: context.data_set.clone(),
+            dir: context.data_dir.path().to_path_buf(),
+        })?;
+    return match read_lines_internal(&path) {
+        Ok(lines) => Ok((path, lines)),
+        Err(source) => Err(PuzzleError::Io {
+            day: context.day,
+            path,
+            source,
+        }),
     };
 }
 
 #[derive(Eq, PartialEq, Clone, Copy)]
 pub enum Mode {
     STANDARD,
-    BENCH(u16),
+    BENCH(BenchConfig),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -111,19 +84,80 @@ pub enum Part {
     Part2,
 }
 
-#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
 pub enum LogLevel {
     ERROR = 0,
-    INFO = 1,
-    DEBUG = 2,
+    WARN = 1,
+    INFO = 2,
+    DEBUG = 3,
+    TRACE = 4,
 }
 
+/// Log level of a day, or of a part of a day, overriding the default one
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelOverride {
+    /// `None` for every day
+    pub day: Option<u8>,
+    /// `None` for both parts, a part override not applying to the global runs of a day (rejected by the command line)
+    pub part: Option<Part>,
+    pub level: LogLevel,
+}
+
+impl LevelOverride {
+    fn applies(&self, day: &u8, part: Option<Part>) -> bool {
+        self.day.is_none_or(|d| d == *day) && (self.part.is_none() || self.part == part)
+    }
+
+    /// Day and part overrides are more specific than day ones, themselves more specific than the global ones
+    fn specificity(&self) -> u8 {
+        self.day.map_or(0, |_| 1) + self.part.map_or(0, |_| 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Dataset {
+    /// The default example of the day
     Test,
+    /// An additional named example, `day_8_2_test.dat` being the example `2`
+    Example(String),
     Real,
 }
 
+impl Dataset {
+    pub fn is_test(&self) -> bool {
+        *self != Dataset::Real
+    }
+
+    /// `test`, `real`, or `test_<name>` for a named example
+    pub fn name(&self) -> String {
+        match self {
+            Dataset::Test => "test".to_string(),
+            Dataset::Example(name) => format!("test_{}", name),
+            Dataset::Real => "real".to_string(),
+        }
+    }
+
+    /// Examples named after a part number only apply to that part
+    pub fn only_part(&self) -> Option<Part> {
+        match self {
+            Dataset::Example(name) if name == "1" => Some(Part::Part1),
+            Dataset::Example(name) if name == "2" => Some(Part::Part2),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Dataset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dataset::Test => write!(f, "Test"),
+            Dataset::Example(name) => write!(f, "Test[{}]", name),
+            Dataset::Real => write!(f, "Real"),
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub enum Active {
     True,
@@ -137,9 +171,16 @@ pub struct Context {
     data_set: Dataset,
     is_debug: bool,
     is_bench: bool,
+    is_record: bool,
     part: Option<Part>,
+    /// Part asked with `--part`, the only one reported for a day solving both parts at once
+    part_restriction: Option<Part>,
+    data_dir: DataDir,
+    logger: &'static Logger,
+    phases: Mutex<Vec<(&'static str, Duration)>>,
 }
 static NB_FAILURES: AtomicUsize = AtomicUsize::new(0);
+static NB_PANICS: AtomicUsize = AtomicUsize::new(0);
 
 #[allow(dead_code)]
 impl Context {
@@ -151,9 +192,14 @@ impl Context {
         return Context::new(day, options, None, data_set);
     }
 
+    /// Context of a puzzle called outside of the runner, only logging the errors
+    pub fn solver(day: u8, part: Option<Part>, data_set: &Dataset) -> Context {
+        Context::new(&day, &RunOption::new(&None).quiet(), part, data_set)
+    }
+
     fn new(day: &u8, options: &RunOption, part: Option<Part>, data_set: &Dataset) -> Context {
-        let log_level = options.get_log_level();
-        let is_debug = options.debug.unwrap_or(false);
+        let log_level = options.get_log_level(day, part);
+        let is_debug = log_level >= LogLevel::DEBUG;
         let is_bench = options
             .mode
             .map(|m| match m {
@@ -161,39 +207,56 @@ impl Context {
                 _ => false,
             })
             .unwrap_or(false);
+        let is_record = options.record.unwrap_or(false);
         return Context {
             log_level: log_level,
             day: *day,
-            data_set: *data_set,
+            data_set: data_set.clone(),
             part: part,
+            part_restriction: options.part_restriction,
             is_debug: is_debug,
             is_bench: is_bench,
+            is_record,
+            data_dir: options.get_data_dir(),
+            logger: Logger::installed(),
+            phases: Mutex::new(vec![]),
         };
     }
 
-    fn log(&self, log_level: LogLevel, print_fct: impl Fn()) {
+    /// The message is only built when the level is enabled
+    fn log(&self, log_level: LogLevel, message_fct: impl FnOnce() -> String) {
         if log_level <= self.log_level {
-            match &self.part {
-                Some(p) => {
-                    print!("[Day {}/{:?}/{:?}]", self.day, p, self.data_set)
-                }
-                None => print!("[Day {}/ALL/{:?}]", self.day, self.data_set),
-            }
-            print_fct();
+            self.logger.log(&Record {
+                level: &log_level,
+                day: self.day,
+                part: self.part,
+                data_set: &self.data_set,
+                message: &message_fct(),
+            });
         }
     }
 
     #[allow(dead_code)]
-    pub fn debug(&self, print_fct: impl Fn()) {
-        self.log(LogLevel::DEBUG, print_fct);
+    pub fn trace(&self, message_fct: impl FnOnce() -> String) {
+        self.log(LogLevel::TRACE, message_fct);
     }
 
-    pub fn error(&self, print_fct: impl Fn()) {
-        self.log(LogLevel::ERROR, print_fct);
+    #[allow(dead_code)]
+    pub fn debug(&self, message_fct: impl FnOnce() -> String) {
+        self.log(LogLevel::DEBUG, message_fct);
     }
 
-    pub fn info(&self, print_fct: impl Fn()) {
-        self.log(LogLevel::INFO, print_fct);
+    pub fn error(&self, message_fct: impl FnOnce() -> String) {
+        self.log(LogLevel::ERROR, message_fct);
+    }
+
+    #[allow(dead_code)]
+    pub fn warn(&self, message_fct: impl FnOnce() -> String) {
+        self.log(LogLevel::WARN, message_fct);
+    }
+
+    pub fn info(&self, message_fct: impl FnOnce() -> String) {
+        self.log(LogLevel::INFO, message_fct);
     }
 
     #[allow(dead_code)]
@@ -201,6 +264,10 @@ impl Context {
         return self.is_debug;
     }
 
+    pub fn part(&self) -> Option<Part> {
+        self.part
+    }
+
     pub fn is_part(&self, part: Part) -> bool {
         return if let Some(p) = self.part { p == part } else { false };
     }
@@ -217,7 +284,7 @@ impl Context {
     }
 
     pub fn is_test(&self) -> bool {
-        return self.data_set == Dataset::Test;
+        return self.data_set.is_test();
     }
 
     pub fn check<T: Eq + Display>(&self, val: T, expected: T) {
@@ -237,6 +304,58 @@ impl Context {
         NB_FAILURES.fetch_add(0, std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Times `fct` as a named phase of the puzzle (e.g. "parse", "part1"), reported by the runner
+    pub fn phase<T>(&self, name: &'static str, fct: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = fct();
+        let elapsed = start.elapsed();
+        let mut phases = self.phases.lock().unwrap();
+        match phases.iter_mut().find(|(phase, _)| *phase == name) {
+            Some((_, duration)) => *duration += elapsed,
+            None => phases.push((name, elapsed)),
+        }
+        result
+    }
+
+    /// Phases timed during the last puzzle call
+    fn take_phases(&self) -> Vec<(&'static str, Duration)> {
+        std::mem::take(&mut *self.phases.lock().unwrap())
+    }
+
+    pub fn get_panics() -> usize {
+        NB_PANICS.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Wrong answers, failed days and panics
+    pub fn get_failures() -> usize {
+        Context::get_errors() + Context::get_panics()
+    }
+
+    /// Calls the puzzle, a panic being reported and counted instead of aborting the run
+    pub fn call<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(&self, fct: &F, lines: &Vec<String>) -> Result<Answer, PuzzleError> {
+        match panics::catch(|| fct(self, lines)) {
+            Ok(result) => result.map_err(|source| PuzzleError::Parse {
+                day: self.day,
+                data_set: self.data_set.clone(),
+                source,
+            }),
+            Err(report) => {
+                NB_PANICS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                log!(error, self, "Panicked {}", report);
+                match &report.backtrace {
+                    Some(backtrace) => log!(debug, self, "Backtrace of the panic\n{}", backtrace),
+                    None => log!(debug, self, "Run with RUST_BACKTRACE=1 for the backtrace of the panic"),
+                }
+                Err(PuzzleError::Panic {
+                    day: self.day,
+                    part: self.part,
+                    data_set: self.data_set.clone(),
+                    report,
+                })
+            }
+        }
+    }
+
     pub fn check_both<T: Eq + Display>(&self, val: (T, T), expected: (T, T)) {
         if val.0 == expected.0 && val.1 == expected.1 {
             log!(info, self, "Result OK ({},{})", val.0, val.1);
@@ -275,80 +394,244 @@ impl Context {
             );
         }
     }
+
+    fn expected(&self, part: Part) -> Option<Value> {
+        AnswersStore::installed().and_then(|store| store.expected(self.day, part, &self.data_set))
+    }
+
+    fn applicable_parts(&self) -> Vec<Part> {
+        match self.part.or(self.data_set.only_part()).or(self.part_restriction) {
+            Some(part) => vec![part],
+            None => vec![Part::Part1, Part::Part2],
+        }
+    }
+
+    /// Fills the expected values of the answer from the answers store, then checks it, returning false if it is wrong or missing
+    pub fn check_answer(&self, answer: &mut Answer) -> bool {
+        let parts = self.applicable_parts();
+        for part in [Part::Part1, Part::Part2] {
+            if !parts.contains(&part) {
+                answer.clear(part);
+            }
+        }
+        for part in &parts {
+            match answer.get_mut(*part) {
+                Some(part_answer) => part_answer.expected = self.expected(*part),
+                None => {
+                    self.missing_answer();
+                    return false;
+                }
+            }
+        }
+        let part_answers: Vec<(Part, &PartAnswer)> = parts.iter().filter_map(|part| answer.get(*part).map(|a| (*part, a))).collect();
+        match part_answers.as_slice() {
+            [(_, part1), (_, part2)] if part1.expected.is_some() && part2.expected.is_some() => self.check_both(
+                (&part1.value, &part2.value),
+                (part1.expected.as_ref().unwrap(), part2.expected.as_ref().unwrap()),
+            ),
+            _ => part_answers.iter().for_each(|(part, part_answer)| match &part_answer.expected {
+                Some(expected) => self.check(&part_answer.value, expected),
+                None => self.unknown_expected(*part, &part_answer.value),
+            }),
+        }
+        part_answers.iter().all(|(_, part_answer)| part_answer.expected.as_ref().is_none_or(|expected| *expected == part_answer.value))
+    }
+
+    fn report(&self, result: &Result<Answer, PuzzleError>, timings: Timings) -> RunReport {
+        let parts = self
+            .applicable_parts()
+            .into_iter()
+            .map(|part| {
+                let part_answer = result.as_ref().ok().and_then(|answer| answer.get(part));
+                let status = match (result, part_answer) {
+                    (Err(PuzzleError::Panic { .. }), _) => Status::Panicked,
+                    (Err(_), _) => Status::Failed,
+                    (Ok(_), None) => Status::Missing,
+                    (Ok(_), Some(PartAnswer { expected: None, .. })) => Status::Unchecked,
+                    (Ok(_), Some(PartAnswer { value, expected: Some(expected) })) if value == expected => Status::Ok,
+                    (Ok(_), Some(_)) => Status::Wrong,
+                };
+                PartReport {
+                    part,
+                    value: part_answer.map(|a| a.value.clone()),
+                    expected: part_answer.and_then(|a| a.expected.clone()).or_else(|| self.expected(part)),
+                    status,
+                }
+            })
+            .collect();
+        RunReport {
+            day: self.day,
+            data_set: self.data_set.clone(),
+            parts,
+            error: result.as_ref().err().map(|error| error.to_string()),
+            read_ms: timings.read_ms,
+            solve_ms: timings.solve_ms,
+            bench: timings.bench,
+            phases: timings.phases,
+        }
+    }
+
+    fn unknown_expected(&self, part: Part, value: &Value) {
+        match AnswersStore::installed().filter(|_| self.is_record) {
+            Some(store) => {
+                store.record(self.day, part, &self.data_set, value);
+                log!(info, self, "Result {} recorded for {:?}", value, part);
+            }
+            None => log!(info, self, "Result {} for {:?} (no expected value)", value, part),
+        }
+    }
+
+    fn missing_answer(&self) {
+        Context::incr_error();
+        log!(error, self, "No result returned");
+    }
+}
+
+/// Runs the puzzle on the context's dataset, for a part or for both at once, and adds its report
+#[allow(dead_code)]
+pub fn run<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(context: Context, fct: &F, mode: &Mode) -> Result<Answer, PuzzleError> {
+    let (result, report) = execute(context, fct, mode);
+    report::add(report);
+    result
 }
 
-pub fn run<F: Fn(&Context, &Vec<String>)>(context: Context, fct: &F, mode: &Mode) {
+/// Runs the puzzle on the context's dataset, returning the outcome with its report
+fn execute<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(context: Context, fct: &F, mode: &Mode) -> (Result<Answer, PuzzleError>, RunReport) {
     log!(info, &context, "Starting");
+    let mut timings = Timings::default();
+    let result = execute_timed(&context, fct, mode, &mut timings);
+    let report = context.report(&result, timings);
+    (result, report)
+}
 
-    let start_read = Instant::now();
-    let lines = to_lines(&context.day, context.part, &context.data_set);
-    let read_duration = start_read.elapsed().as_secs_f32() * 1000.0;
-    let nb_max = match *mode {
-        Mode::BENCH(nb) => nb,
-        _ => 1,
-    };
+/// A run of a day on one dataset, for one part or both parts
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub day: u8,
+    pub part: Option<Part>,
+    pub data_set: Dataset,
+}
+
+/// Result of a job, with the log output it captured
+pub struct JobOutcome {
+    pub job: Job,
+    result: Result<Answer, PuzzleError>,
+    report: RunReport,
+    output: String,
+    duration_ms: f32,
+}
+
+/// Runs the job, its log output being captured instead of printed when `capture` is set
+pub fn run_job<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(job: &Job, fct: &F, options: &RunOption, capture: bool) -> JobOutcome {
     let start = Instant::now();
-    let mut count = 0;
-    while count < nb_max {
-        count += 1;
-        fct(&context, &lines);
-    }
-    let duration = start.elapsed().as_secs_f32() * 1000.0;
-
-    log!(
-        info,
-        context,
-        "Duration {:.2} ms (avg {:.2} for #{} iterations) and {:.2} ms for read",
-        duration,
-        duration / nb_max as f32,
-        count,
-        read_duration
-    );
+    let context = Context::new(&job.day, options, job.part, &job.data_set);
+    let ((result, report), output) = if capture {
+        output::capture(|| execute(context, fct, options.get_mode()))
+    } else {
+        (execute(context, fct, options.get_mode()), String::new())
+    };
+    JobOutcome {
+        job: job.clone(),
+        result,
+        report,
+        output,
+        duration_ms: start.elapsed().as_secs_f32() * 1000.0,
+    }
 }
 
-#[allow(dead_code)]
-pub fn run_simult<F: Fn(&Context, &Vec<String>)>(context: Context, fct: &F, mode: &Mode) {
-    log!(info, context, "Starting");
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub read_ms: f32,
+    /// Duration of the puzzle call, the median one in bench mode
+    pub solve_ms: f32,
+    pub bench: Option<Stats>,
+    /// Duration of the phases timed by the puzzle, the median ones in bench mode
+    pub phases: Vec<(&'static str, f32)>,
+}
 
+fn execute_timed<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(
+    context: &Context,
+    fct: &F,
+    mode: &Mode,
+    timings: &mut Timings,
+) -> Result<Answer, PuzzleError> {
     let start_read = Instant::now();
-    let lines = to_lines(&context.day, None, &context.data_set);
-    let read_duration = start_read.elapsed().as_secs_f32() * 1000.0;
-
-    let nb_max = match *mode {
-        Mode::BENCH(nb) => nb,
-        _ => 1,
-    };
-    let start = Instant::now();
-    let mut count = 0;
-    while count < nb_max {
-        count += 1;
-        fct(&context, &lines);
-    }
-    let duration = start.elapsed().as_secs_f32() * 1000.0;
-    log!(
-        info,
-        context,
-        "Duration {:.2} ms (avg {:.2} for #{} iterations) and {:.2} ms for read",
-        duration,
-        duration / nb_max as f32,
-        count,
-        read_duration
-    )
+    let lines = to_lines(context)?;
+    timings.read_ms = start_read.elapsed().as_secs_f32() * 1000.0;
+    match mode {
+        Mode::STANDARD => {
+            let start = Instant::now();
+            let mut answer = context.call(fct, &lines)?;
+            timings.solve_ms = start.elapsed().as_secs_f32() * 1000.0;
+            timings.phases = context.take_phases().into_iter().map(|(name, duration)| (name, duration.as_secs_f32() * 1000.0)).collect();
+            context.check_answer(&mut answer);
+            log!(
+                info,
+                context,
+                "Duration {:.2} ms{} and {:.2} ms for read",
+                timings.solve_ms,
+                report::format_phases(&timings.phases),
+                timings.read_ms
+            );
+            Ok(answer)
+        }
+        Mode::BENCH(config) => {
+            // the first call verifies the answer, the benchmark being pointless if it is wrong
+            let mut answer = context.call(fct, &lines)?;
+            context.take_phases();
+            if !context.check_answer(&mut answer) {
+                log!(error, context, "Bench skipped, the answer is wrong");
+                return Ok(answer);
+            }
+            let mut phases: Vec<(&'static str, Vec<f64>)> = vec![];
+            let iterations = config.measure(|| {
+                // black boxed so that the optimizer can neither drop the call nor hoist it out of the loop
+                black_box(context.call(fct, black_box(&lines))?);
+                for (name, duration) in context.take_phases() {
+                    match phases.iter_mut().find(|(phase, _)| *phase == name) {
+                        Some((_, durations)) => durations.push(duration.as_secs_f64() * 1000.0),
+                        None => phases.push((name, vec![duration.as_secs_f64() * 1000.0])),
+                    }
+                }
+                Ok(())
+            })?;
+            timings.bench = Stats::compute(&iterations);
+            timings.phases = phases
+                .iter()
+                .filter_map(|(name, durations)| Stats::compute(durations).map(|stats| (*name, stats.median as f32)))
+                .collect();
+            if let Some(stats) = &timings.bench {
+                timings.solve_ms = stats.median as f32;
+                log!(info, context, "Bench {}{} and {:.2} ms for read", stats, report::format_phases(&timings.phases), timings.read_ms);
+            }
+            Ok(answer)
+        }
+    }
 }
 
-pub fn to_lines(day: &u8, part: Option<Part>, data_set: &Dataset) -> Vec<String> {
-    return read_lines(day, part, data_set)
-        .map(|lines| lines.map(|l| l.unwrap()).collect())
-        .unwrap_or(vec![]);
+pub fn to_lines(context: &Context) -> Result<Vec<String>, PuzzleError> {
+    let (path, lines) = read_lines(context)?;
+    return lines.collect::<Result<Vec<String>, Error>>().map_err(|source| PuzzleError::Io {
+        day: context.day,
+        path,
+        source,
+    });
 }
 
 pub type DaysRestriction<'a> = &'a Option<Vec<u8>>;
+#[derive(Clone, Copy)]
 pub struct RunOption<'a> {
     active: Option<bool>,
     mode: Option<Mode>,
     debug: Option<bool>,
+    quiet: Option<bool>,
+    level_overrides: &'a [LevelOverride],
+    record: Option<bool>,
     part_restriction: Option<Part>,
+    dataset_restriction: Option<&'a Dataset>,
     days_restriction: DaysRestriction<'a>,
+    data_dir: Option<&'a DataDir>,
+    threads: Option<usize>,
 }
 
 impl<'a> RunOption<'a> {
@@ -358,57 +641,86 @@ impl<'a> RunOption<'a> {
     pub fn new(days_restriction: DaysRestriction<'a>) -> RunOption<'a> {
         RunOption {
             debug: None,
+            quiet: None,
+            level_overrides: &[],
+            record: None,
             mode: None,
             active: None,
             part_restriction: None,
+            dataset_restriction: None,
             days_restriction,
+            data_dir: None,
+            threads: None,
         }
     }
 
     #[allow(dead_code)]
     pub fn disabled() -> RunOption<'a> {
         RunOption {
-            debug: None,
-            mode: None,
             active: Some(false),
-            part_restriction: None,
-            days_restriction: &None,
+            ..RunOption::new(&None)
         }
     }
-    #[allow(dead_code)]
+
     pub fn debug(&self) -> RunOption<'a> {
-        RunOption {
-            debug: Some(true),
-            mode: self.mode,
-            active: self.active,
-            part_restriction: self.part_restriction,
-            days_restriction: self.days_restriction,
-        }
+        RunOption { debug: Some(true), ..*self }
     }
 
-    #[allow(dead_code)]
-    pub fn bench(&self, nb: u16) -> RunOption<'a> {
+    /// Only the errors are logged, and the runner only prints the failures
+    pub fn quiet(&self) -> RunOption<'a> {
+        RunOption { quiet: Some(true), ..*self }
+    }
+
+    pub fn level_overrides(&self, level_overrides: &'a [LevelOverride]) -> RunOption<'a> {
+        RunOption { level_overrides, ..*self }
+    }
+
+    pub fn record(&self) -> RunOption<'a> {
+        RunOption { record: Some(true), ..*self }
+    }
+
+    pub fn bench(&self, config: BenchConfig) -> RunOption<'a> {
         RunOption {
-            active: self.active,
-            mode: Some(Mode::BENCH(nb)),
-            debug: self.debug,
-            part_restriction: self.part_restriction,
-            days_restriction: self.days_restriction,
+            mode: Some(Mode::BENCH(config)),
+            ..*self
         }
     }
 
-    #[allow(dead_code)]
     pub fn only(&self, part: Part) -> RunOption<'a> {
         RunOption {
-            active: self.active,
-            mode: self.mode,
-            debug: self.debug,
             part_restriction: Some(part),
-            days_restriction: self.days_restriction,
+            ..*self
         }
     }
 
-    fn is_active(&self, day: &u8) -> bool {
+    /// `Dataset::Test` enables every example of the day, while `Dataset::Example` enables only that one
+    pub fn only_dataset(&self, dataset: &'a Dataset) -> RunOption<'a> {
+        RunOption {
+            dataset_restriction: Some(dataset),
+            ..*self
+        }
+    }
+
+    pub fn data_dir(&self, data_dir: &'a DataDir) -> RunOption<'a> {
+        RunOption {
+            data_dir: Some(data_dir),
+            ..*self
+        }
+    }
+
+    /// Runs the jobs on a pool of `threads` threads
+    pub fn parallel(&self, threads: usize) -> RunOption<'a> {
+        RunOption {
+            threads: Some(threads),
+            ..*self
+        }
+    }
+
+    pub fn get_threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    pub fn is_active(&self, day: &u8) -> bool {
         if !self.active.unwrap_or(true) {
             return false;
         }
@@ -425,12 +737,24 @@ impl<'a> RunOption<'a> {
         self.mode.as_ref().unwrap_or(&Mode::STANDARD)
     }
 
-    fn get_log_level(&self) -> LogLevel {
-        if self.is_debug() {
+    /// The most specific override applying to the day and part, or the level given by the quiet and debug flags
+    fn get_log_level(&self, day: &u8, part: Option<Part>) -> LogLevel {
+        let default = if self.is_quiet() {
+            LogLevel::ERROR
+        } else if self.is_debug() {
             LogLevel::DEBUG
         } else {
             LogLevel::INFO
-        }
+        };
+        self.level_overrides
+            .iter()
+            .filter(|level_override| level_override.applies(day, part))
+            .max_by_key(|level_override| level_override.specificity())
+            .map_or(default, |level_override| level_override.level)
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self.quiet.unwrap_or(false)
     }
 
     fn is_debug(&self) -> bool {
@@ -440,53 +764,138 @@ impl<'a> RunOption<'a> {
     fn is_part_enabled(&self, part: Part) -> bool {
         self.part_restriction.is_none() || self.part_restriction.unwrap() == part
     }
-}
 
-pub fn run_all<F: Fn(&Context, &Vec<String>)>(day: &u8, fct: &F, options: RunOption) {
-    if !options.is_active(day) {
-        return;
+    fn is_dataset_enabled(&self, dataset: &Dataset) -> bool {
+        match self.dataset_restriction {
+            None => true,
+            Some(Dataset::Test) => dataset.is_test(),
+            Some(restriction) => restriction == dataset,
+        }
     }
 
-    let mode = options.get_mode();
+    fn get_data_dir(&self) -> DataDir {
+        self.data_dir.cloned().unwrap_or_else(|| DataDir::resolve(None))
+    }
 
-    println!("");
-    println!("");
-    println!("[Day {}] run per part", day);
-    let start = Instant::now();
-    if options.is_part_enabled(Part::Part1) {
-        run(Context::new_part(day, &options, Part::Part1, &Dataset::Test), &fct, mode);
-        println!("");
-        run(Context::new_part(day, &options, Part::Part1, &Dataset::Real), &fct, mode);
-        println!("");
+    /// Default example, named examples then real input, restricted to the ones applicable to the part
+    fn get_datasets(&self, day: &u8, part: Option<Part>) -> Vec<Dataset> {
+        let data_dir = self.get_data_dir();
+        [Dataset::Test]
+            .into_iter()
+            .chain(data_dir.examples(*day).into_iter().map(Dataset::Example))
+            .chain([Dataset::Real])
+            .filter(|data_set| part.is_none() || data_set.only_part().is_none() || data_set.only_part() == part)
+            .filter(|data_set| *data_set != Dataset::Test || part.is_none_or(|part| !data_dir.has_part_example(*day, part)))
+            .filter(|data_set| self.is_dataset_enabled(data_set))
+            .collect()
     }
-    if options.is_part_enabled(Part::Part2) {
-        run(Context::new_part(day, &options, Part::Part2, &Dataset::Test), &fct, mode);
-        println!("");
-        run(Context::new_part(day, &options, Part::Part2, &Dataset::Real), &fct, mode);
-        println!("");
+
+    /// Jobs of an active day: each enabled part on its datasets, or each dataset for both parts when `per_part` isn't set
+    pub fn get_jobs(&self, day: &u8, per_part: bool) -> Vec<Job> {
+        if !self.is_active(day) {
+            return vec![];
+        }
+        let parts = match per_part {
+            true => [Part::Part1, Part::Part2].into_iter().filter(|part| self.is_part_enabled(*part)).map(Some).collect(),
+            false => vec![None],
+        };
+        parts
+            .into_iter()
+            .flat_map(|part| {
+                // a day solving both parts at once is run for the datasets of the requested part only
+                self.get_datasets(day, part.or(self.part_restriction)).into_iter().map(move |data_set| Job {
+                    day: *day,
+                    part,
+                    data_set,
+                })
+            })
+            .collect()
     }
-    let duration = start.elapsed().as_secs_f32() * 1000.0;
+}
 
-    println!("[Day {}] done in {:.2} ms", day, duration);
+/// Runs the enabled parts and datasets of a day, the day failing with its first error
+pub fn run_all<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(
+    day: &u8,
+    fct: &F,
+    options: RunOption,
+) -> Result<Vec<(Dataset, Answer)>, PuzzleError> {
+    if !options.is_active(day) {
+        return Ok(vec![]);
+    }
+    let jobs = options.get_jobs(day, true);
+    report_day(day, true, jobs.iter().map(|job| run_job(job, fct, &options, false)), &options)
 }
 
 #[allow(dead_code)]
-pub fn run_all_simult<F: Fn(&Context, &Vec<String>)>(day: &u8, fct: &F, options: RunOption) {
+pub fn run_all_simult<F: Fn(&Context, &Vec<String>) -> Result<Answer, ParseError>>(
+    day: &u8,
+    fct: &F,
+    options: RunOption,
+) -> Result<Vec<(Dataset, Answer)>, PuzzleError> {
     if !options.is_active(day) {
-        return;
+        return Ok(vec![]);
     }
-    let mode = options.get_mode();
-    println!("");
-    println!("");
-    println!("[Day {}] run global", day);
-    let start = Instant::now();
-    run_simult(Context::new_all(day, &options, &Dataset::Test), fct, mode);
-    println!("");
-    run_simult(Context::new_all(day, &options, &Dataset::Real), fct, mode);
-    let duration = start.elapsed().as_secs_f32() * 1000.0;
-    println!("");
+    let jobs = options.get_jobs(day, false);
+    report_day(day, false, jobs.iter().map(|job| run_job(job, fct, &options, false)), &options)
+}
 
-    println!("[Day {}] done in {:.2} ms", day, duration);
+/// Prints the output of the jobs of a day in order and adds their reports, merging the answers per dataset.
+/// Every job is run and reported even after an error, so that a run gives the same report whatever the number of threads,
+/// the day failing with its first error.
+/// In quiet mode, only the failures are printed.
+pub fn report_day(
+    day: &u8,
+    per_part: bool,
+    outcomes: impl Iterator<Item = JobOutcome>,
+    options: &RunOption,
+) -> Result<Vec<(Dataset, Answer)>, PuzzleError> {
+    let verbose = !options.is_quiet();
+    if verbose {
+        outln!();
+        outln!();
+        outln!("[Day {}] run {}", day, if per_part { "per part" } else { "global" });
+    }
+    let mut duration = 0.0;
+    let mut answers: Vec<(Dataset, Answer)> = vec![];
+    let mut first_error = None;
+    for outcome in outcomes {
+        output::write(format_args!("{}", outcome.output));
+        report::add(outcome.report);
+        duration += outcome.duration_ms;
+        match keep_going(day, outcome.result) {
+            Ok(Some(answer)) => match answers.iter_mut().find(|(existing_set, _)| *existing_set == outcome.job.data_set) {
+                Some((_, existing)) => *existing = std::mem::take(existing).merge(answer),
+                None => answers.push((outcome.job.data_set, answer)),
+            },
+            Ok(None) => {}
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
+        if verbose {
+            outln!();
+        }
+    }
+    if verbose {
+        outln!("[Day {}] done in {:.2} ms", day, duration);
+    }
+    match first_error {
+        Some(error) => Err(error),
+        None => Ok(answers),
+    }
+}
+
+/// A panicking run is skipped (it was already reported), while any other error fails the whole day
+fn keep_going<T>(day: &u8, result: Result<T, PuzzleError>) -> Result<Option<T>, PuzzleError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(PuzzleError::Panic { .. }) => Ok(None),
+        Err(error) => {
+            Context::incr_error();
+            outln!("[Day {}] failed, {}", day, error);
+            Err(error)
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::{env, fs, thread};
+use std::time::{Duration, SystemTime};
+
+use advent_of_code_2023::days::{DayDef, SOURCE_DIR};
+use advent_of_code_2023::inputs::DataDir;
+use advent_of_code_2023::report::{self, RunReport, Status};
+use advent_of_code_2023::utils::RunOption;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time of the watched files, `None` for a file that doesn't exist (yet)
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn source_file(day: u8) -> PathBuf {
+    Path::new(SOURCE_DIR).join(format!("day{:02}.rs", day))
+}
+
+/// Input files of the day, the examples being listed again each time so that new ones are picked up
+fn inputs_snapshot(data_dir: &DataDir, day: u8) -> Snapshot {
+    data_dir
+        .input_files(day)
+        .into_iter()
+        .map(|path| {
+            let time = modified(&path);
+            (path, time)
+        })
+        .collect()
+}
+
+/// `PASS 4 ok in 12.30 ms`, or `FAIL 2 ok, 1 wrong, 1 panicked in 12.30 ms`
+fn summary(reports: &[RunReport]) -> String {
+    let statuses: Vec<Status> = reports.iter().flat_map(|report| report.parts.iter().map(|part| part.status)).collect();
+    let counts: Vec<String> = [
+        Status::Ok,
+        Status::Unchecked,
+        Status::Wrong,
+        Status::Missing,
+        Status::Failed,
+        Status::Panicked,
+    ]
+    .iter()
+    .map(|status| (status, statuses.iter().filter(|s| *s == status).count()))
+    .filter(|(_, count)| *count > 0)
+    .map(|(status, count)| format!("{} {}", count, status.name()))
+    .collect();
+    format!(
+        "{} {} in {:.2} ms",
+        if statuses.iter().any(|status| status.is_failure()) {
+            "FAIL"
+        } else {
+            "PASS"
+        },
+        counts.join(", "),
+        reports.iter().map(|report| report.read_ms + report.solve_ms).sum::<f32>()
+    )
+}
+
+/// Builds the binary again with the profile it was built with, returning whether it succeeded
+fn rebuild() -> bool {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.args(["build", "--manifest-path", concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    match command.status() {
+        Ok(status) => status.success(),
+        Err(e) => {
+            println!("[Watch] Cannot run cargo: {}", e);
+            false
+        }
+    }
+}
+
+/// Replaces this process with the rebuilt binary, run with the same arguments
+#[cfg(unix)]
+fn restart(exe: &Path) -> ! {
+    use std::os::unix::process::CommandExt;
+
+    // `exec` only returns on failure
+    let e = Command::new(exe).args(env::args_os().skip(1)).exec();
+    println!("[Watch] Cannot restart {}: {}", exe.display(), e);
+    process::exit(1)
+}
+
+/// Runs the rebuilt binary with the same arguments, exiting with its status as processes can't be replaced
+#[cfg(not(unix))]
+fn restart(exe: &Path) -> ! {
+    match Command::new(exe).args(env::args_os().skip(1)).status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            println!("[Watch] Cannot restart {}: {}", exe.display(), e);
+            process::exit(1)
+        }
+    }
+}
+
+fn run_day(def: &DayDef, options: RunOption) {
+    // failures are reported by the runner, the watch only sums them up
+    let _ = def.run(options);
+    println!("[Watch] Day {} {}", def.day, summary(&report::take()));
+}
+
+/// Runs the days, then runs each of them again whenever one of its input files changes.
+/// The binary can't reload a modified puzzle, so a change of its source rebuilds it with cargo and restarts it with the same arguments,
+/// the watch going on with the current binary when the build fails.
+pub fn watch(days: &[&DayDef], options: RunOption, data_dir: &DataDir) {
+    // resolved before a rebuild replaces the file
+    let exe = env::current_exe().ok();
+    let mut inputs: Vec<Snapshot> = days.iter().map(|def| inputs_snapshot(data_dir, def.day)).collect();
+    let mut sources: Vec<Option<SystemTime>> = days.iter().map(|def| modified(&source_file(def.day))).collect();
+    days.iter().for_each(|def| run_day(def, options));
+    println!("[Watch] watching {} for changes", data_dir.path().display());
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        for (index, def) in days.iter().enumerate() {
+            let source_time = modified(&source_file(def.day));
+            if source_time != sources[index] {
+                sources[index] = source_time;
+                println!("[Watch] {} changed, rebuilding", source_file(def.day).display());
+                match &exe {
+                    Some(exe) if rebuild() => restart(exe),
+                    Some(_) => println!("[Watch] build failed, still running the previous build"),
+                    None => println!("[Watch] cannot locate the binary, rebuild and restart to run the change"),
+                }
+            }
+            let snapshot = inputs_snapshot(data_dir, def.day);
+            if snapshot != inputs[index] {
+                inputs[index] = snapshot;
+                run_day(def, options);
+            }
+        }
+    }
+}
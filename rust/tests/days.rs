@@ -0,0 +1,124 @@
+//! Every day checked against the answers of `data/answers.toml`, one test per day, part and dataset (e.g. `cargo test day17`).
+//! A part is checked on its own example when it has one (e.g. `day_1_2_test.dat`), on the default one otherwise.
+//! An example without input or expected answer fails, as does a real input without expected answer; a missing real
+//! input only skips its test, the real inputs not being always available. A day without answers yet is listed with
+//! `#[ignore]`, as `new-day` does.
+
+use std::fs;
+use std::path::PathBuf;
+
+use advent_of_code_2023::answers::AnswersStore;
+use advent_of_code_2023::days;
+use advent_of_code_2023::inputs::DataDir;
+use advent_of_code_2023::utils::{Dataset, Part};
+
+fn check(day: u8, part: Part, data_set: &Dataset, path: PathBuf) {
+    let data_dir = DataDir::resolve(None);
+    let store = AnswersStore::load(&data_dir.answers_file()).unwrap_or_else(|message| panic!("{}", message));
+    let expected = store
+        .expected(day, part, data_set)
+        .unwrap_or_else(|| panic!("Day {} {:?} {}: no expected answer in {}", day, part, data_set, data_dir.answers_file().display()));
+    let content = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Cannot read {}: {}", path.display(), e));
+    let lines: Vec<String> = content.lines().map(String::from).collect();
+    let def = days::get(day).unwrap_or_else(|| panic!("Day {} isn't registered", day));
+    let answer = def.solve_part(part, data_set, &lines).unwrap_or_else(|error| panic!("{}", error));
+    let value = answer.get(part).map(|part_answer| part_answer.value.to_string());
+    assert_eq!(value, Some(expected.to_string()), "Day {} {:?} on {}", day, part, data_set);
+}
+
+/// The example of the part, its own one when it has one
+fn check_example(day: u8, part: Part) {
+    let data_dir = DataDir::resolve(None);
+    let data_set = data_dir
+        .examples(day)
+        .into_iter()
+        .map(Dataset::Example)
+        .find(|data_set| data_set.only_part() == Some(part))
+        .unwrap_or(Dataset::Test);
+    let path = data_dir
+        .find_input(day, Some(part), &data_set)
+        .unwrap_or_else(|| panic!("Day {} {:?} {}: no input file in {}", day, part, data_set, data_dir.path().display()));
+    check(day, part, &data_set, path);
+}
+
+fn check_real(day: u8, part: Part) {
+    let data_dir = DataDir::resolve(None);
+    match data_dir.find_input(day, Some(part), &Dataset::Real) {
+        Some(path) => check(day, part, &Dataset::Real, path),
+        None => eprintln!("Day {} {:?}: skipped, no real input in {}", day, part, data_dir.path().display()),
+    }
+}
+
+macro_rules! day_tests {
+    ($($(#[$attr:meta])* $module:ident: $day:expr),* $(,)?) => {
+        $(
+            mod $module {
+                use super::*;
+
+                #[test]
+                $(#[$attr])*
+                fn part1_test() {
+                    check_example($day, Part::Part1);
+                }
+
+                #[test]
+                $(#[$attr])*
+                fn part1_real() {
+                    check_real($day, Part::Part1);
+                }
+
+                #[test]
+                $(#[$attr])*
+                fn part2_test() {
+                    check_example($day, Part::Part2);
+                }
+
+                #[test]
+                $(#[$attr])*
+                fn part2_real() {
+                    check_real($day, Part::Part2);
+                }
+            }
+        )*
+
+        #[test]
+        fn every_registered_day_is_tested() {
+            let tested: Vec<u8> = vec![$($day),*];
+            for def in days::DAYS {
+                assert!(tested.contains(&def.day), "Day {} has no tests, add it to day_tests! in {}", def.day, file!());
+            }
+        }
+
+        /// Only the examples of a single part are checked, any other one would go untested
+        #[test]
+        fn every_example_is_tested() {
+            let data_dir = DataDir::resolve(None);
+            for day in [$($day),*] {
+                for name in data_dir.examples(day) {
+                    let data_set = Dataset::Example(name);
+                    assert!(data_set.only_part().is_some(), "Day {} {}: only the examples of a part (1 or 2) are tested", day, data_set);
+                }
+            }
+        }
+    };
+}
+
+day_tests! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+}
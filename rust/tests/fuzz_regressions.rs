@@ -0,0 +1,33 @@
+//! Inputs that crashed a day under the fuzzer (`fuzz/regressions/dayNN/`), replayed once fixed so that they can't crash it again.
+//! The fuzzer itself needs a nightly toolchain, see `fuzz/src/lib.rs`.
+
+use std::fs;
+use std::path::Path;
+
+use advent_of_code_2023::days;
+use advent_of_code_2023::error::PuzzleError;
+use advent_of_code_2023::utils::Dataset;
+
+const REGRESSIONS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/regressions");
+
+#[test]
+fn saved_crashes_no_longer_panic() {
+    for def in days::DAYS {
+        let dir = Path::new(REGRESSIONS_DIR).join(format!("day{:02}", def.day));
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.map(|entry| entry.unwrap().path()) {
+            // the fuzzer skips the inputs that aren't UTF-8
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let lines: Vec<String> = content.lines().map(String::from).collect();
+            for result in def.solve_parts(&Dataset::Real, &lines) {
+                if let Err(PuzzleError::Panic { report, .. }) = result {
+                    panic!("Day {} panicked {} on {}", def.day, report, path.display());
+                }
+            }
+        }
+    }
+}